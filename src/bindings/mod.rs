@@ -0,0 +1,331 @@
+#[cfg(test)]
+mod tests;
+
+use piston_window::{Key, MouseButton};
+use std::collections::{BTreeMap, HashMap};
+
+// everything a key or mouse button can be bound to in the window
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Command {
+    Reveal,
+    Flag,
+    Chord,
+    Reset,
+    Pause,
+    Undo,
+    Hint,
+    Scores,
+    Skin,
+    Palette,
+    HighContrast,
+    Patterns,
+    Export,
+    Bindings,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    NextPreset,
+    PreviousPreset,
+    // index into the preset registry
+    Preset(usize),
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Input {
+    Key(Key),
+    Mouse(MouseButton),
+}
+
+// in the order the bindings screen lists them, with their names in the config
+pub const COMMANDS: [(&str, Command); 32] = [
+    ("reveal", Command::Reveal),
+    ("flag", Command::Flag),
+    ("chord", Command::Chord),
+    ("reset", Command::Reset),
+    ("pause", Command::Pause),
+    ("undo", Command::Undo),
+    ("hint", Command::Hint),
+    ("scores", Command::Scores),
+    ("skin", Command::Skin),
+    ("palette", Command::Palette),
+    ("high_contrast", Command::HighContrast),
+    ("patterns", Command::Patterns),
+    ("export", Command::Export),
+    ("bindings", Command::Bindings),
+    ("zoom_in", Command::ZoomIn),
+    ("zoom_out", Command::ZoomOut),
+    ("zoom_reset", Command::ZoomReset),
+    ("next_preset", Command::NextPreset),
+    ("previous_preset", Command::PreviousPreset),
+    ("preset_1", Command::Preset(0)),
+    ("preset_2", Command::Preset(1)),
+    ("preset_3", Command::Preset(2)),
+    ("preset_4", Command::Preset(3)),
+    ("preset_5", Command::Preset(4)),
+    ("preset_6", Command::Preset(5)),
+    ("preset_7", Command::Preset(6)),
+    ("preset_8", Command::Preset(7)),
+    ("preset_9", Command::Preset(8)),
+    ("cursor_up", Command::CursorUp),
+    ("cursor_down", Command::CursorDown),
+    ("cursor_left", Command::CursorLeft),
+    ("cursor_right", Command::CursorRight),
+];
+
+const DEFAULT_BINDINGS: [(Input, Command); 41] = [
+    (Input::Mouse(MouseButton::Left), Command::Reveal),
+    (Input::Key(Key::Space), Command::Reveal),
+    (Input::Key(Key::Return), Command::Reveal),
+    (Input::Mouse(MouseButton::Right), Command::Flag),
+    (Input::Key(Key::F), Command::Flag),
+    (Input::Mouse(MouseButton::Middle), Command::Chord),
+    (Input::Key(Key::D), Command::Chord),
+    (Input::Key(Key::R), Command::Reset),
+    (Input::Key(Key::P), Command::Pause),
+    (Input::Key(Key::U), Command::Undo),
+    (Input::Key(Key::H), Command::Hint),
+    (Input::Key(Key::S), Command::Scores),
+    (Input::Key(Key::T), Command::Skin),
+    (Input::Key(Key::C), Command::Palette),
+    (Input::Key(Key::V), Command::HighContrast),
+    (Input::Key(Key::G), Command::Patterns),
+    (Input::Key(Key::E), Command::Export),
+    (Input::Key(Key::B), Command::Bindings),
+    (Input::Key(Key::Equals), Command::ZoomIn),
+    (Input::Key(Key::NumPadPlus), Command::ZoomIn),
+    (Input::Key(Key::Minus), Command::ZoomOut),
+    (Input::Key(Key::NumPadMinus), Command::ZoomOut),
+    (Input::Key(Key::D0), Command::ZoomReset),
+    (Input::Key(Key::PageUp), Command::NextPreset),
+    (Input::Key(Key::PageDown), Command::PreviousPreset),
+    (Input::Key(Key::D1), Command::Preset(0)),
+    (Input::Key(Key::D2), Command::Preset(1)),
+    (Input::Key(Key::D3), Command::Preset(2)),
+    (Input::Key(Key::D4), Command::Preset(3)),
+    (Input::Key(Key::D5), Command::Preset(4)),
+    (Input::Key(Key::D6), Command::Preset(5)),
+    (Input::Key(Key::D7), Command::Preset(6)),
+    (Input::Key(Key::D8), Command::Preset(7)),
+    (Input::Key(Key::D9), Command::Preset(8)),
+    // arrow keys or the terminal's HJKL, with H taken by hints
+    (Input::Key(Key::Up), Command::CursorUp),
+    (Input::Key(Key::K), Command::CursorUp),
+    (Input::Key(Key::Down), Command::CursorDown),
+    (Input::Key(Key::J), Command::CursorDown),
+    (Input::Key(Key::Left), Command::CursorLeft),
+    (Input::Key(Key::Right), Command::CursorRight),
+    (Input::Key(Key::L), Command::CursorRight),
+];
+
+const KEY_NAMES: [(&str, Key); 20] = [
+    ("Space", Key::Space),
+    ("Return", Key::Return),
+    ("Backspace", Key::Backspace),
+    ("Tab", Key::Tab),
+    ("Up", Key::Up),
+    ("Down", Key::Down),
+    ("Left", Key::Left),
+    ("Right", Key::Right),
+    ("PageUp", Key::PageUp),
+    ("PageDown", Key::PageDown),
+    ("Home", Key::Home),
+    ("End", Key::End),
+    ("Insert", Key::Insert),
+    ("Delete", Key::Delete),
+    ("Equals", Key::Equals),
+    ("Minus", Key::Minus),
+    ("Comma", Key::Comma),
+    ("Period", Key::Period),
+    ("NumPadPlus", Key::NumPadPlus),
+    ("NumPadMinus", Key::NumPadMinus),
+];
+
+const MOUSE_NAMES: [(&str, MouseButton); 8] = [
+    ("MouseLeft", MouseButton::Left),
+    ("MouseRight", MouseButton::Right),
+    ("MouseMiddle", MouseButton::Middle),
+    ("MouseX1", MouseButton::X1),
+    ("MouseX2", MouseButton::X2),
+    ("Mouse6", MouseButton::Button6),
+    ("Mouse7", MouseButton::Button7),
+    ("Mouse8", MouseButton::Button8),
+];
+
+pub fn parse_command(name: &str) -> Option<Command> {
+    COMMANDS.iter().find(|x| x.0 == name).map(|x| x.1)
+}
+
+// input names as written in the config, single letters and digits, F1 to F12, names such as
+// Space and MouseLeft, or Key<code> for anything else
+pub fn parse_input(name: &str) -> Option<Input> {
+    let lower = name.to_lowercase();
+    let mut chars = lower.chars();
+
+    if let (Some(x), None) = (chars.next(), chars.next()) {
+        if x.is_ascii_alphanumeric() {
+            return Some(Input::Key(Key::from(x as u32)));
+        }
+    }
+
+    if let Some(n) = lower.strip_prefix('f').and_then(|x| x.parse::<u32>().ok()) {
+        if (1..=12).contains(&n) {
+            return Some(Input::Key(Key::from(Key::F1 as u32 + n - 1)));
+        }
+    }
+
+    if let Some(code) = lower.strip_prefix("key").and_then(|x| x.parse::<u32>().ok()) {
+        return Some(Input::Key(Key::from(code)));
+    }
+
+    if let Some(&(_, key)) = KEY_NAMES.iter().find(|x| x.0.to_lowercase() == lower) {
+        return Some(Input::Key(key));
+    }
+
+    // enter is another name for return
+    if lower == "enter" {
+        return Some(Input::Key(Key::Return));
+    }
+
+    MOUSE_NAMES
+        .iter()
+        .find(|x| x.0.to_lowercase() == lower)
+        .map(|&(_, button)| Input::Mouse(button))
+}
+
+pub fn get_input_name(input: Input) -> String {
+    match input {
+        Input::Key(key) => {
+            let code = key as u32;
+
+            if let Some(&(name, _)) = KEY_NAMES.iter().find(|x| x.1 == key) {
+                name.to_owned()
+            } else if code < 128 && (code as u8 as char).is_ascii_alphanumeric() {
+                (code as u8 as char).to_ascii_uppercase().to_string()
+            } else if code >= Key::F1 as u32 && code <= Key::F12 as u32 {
+                format!("F{}", code - Key::F1 as u32 + 1)
+            } else {
+                format!("Key{}", code)
+            }
+        }
+        Input::Mouse(button) => MOUSE_NAMES
+            .iter()
+            .find(|x| x.1 == button)
+            .map_or_else(|| format!("{:?}", button), |x| x.0.to_owned()),
+    }
+}
+
+// which command every key and mouse button runs, one command per input
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bindings {
+    table: HashMap<Input, Command>,
+}
+
+impl Default for Bindings {
+    fn default() -> Bindings {
+        Bindings {
+            table: DEFAULT_BINDINGS.iter().cloned().collect(),
+        }
+    }
+}
+
+impl Bindings {
+    // the defaults with the reveal and flag mouse buttons swapped if left handed, then the
+    // config's, which replace every default input of the commands they name
+    //
+    //   reveal = "Space MouseRight"
+    pub fn from_config(keys: &BTreeMap<String, String>, left_handed: bool) -> Bindings {
+        let mut bindings = Bindings::default();
+        if left_handed {
+            bindings.swap_mouse_buttons();
+        }
+
+        for (name, inputs) in keys {
+            let command = match parse_command(name) {
+                Some(command) => command,
+                None => {
+                    eprintln!("unknown action {} in config", name);
+                    continue;
+                }
+            };
+
+            let mut parsed = Vec::new();
+            for input in inputs.split_whitespace() {
+                match parse_input(input) {
+                    Some(input) => parsed.push(input),
+                    None => eprintln!("unknown key {} for {} in config", input, name),
+                }
+            }
+
+            bindings.set(command, &parsed);
+        }
+
+        bindings
+    }
+
+    pub fn get(&self, input: Input) -> Option<Command> {
+        self.table.get(&input).cloned()
+    }
+
+    // sorted so they read the same every time
+    pub fn get_inputs(&self, command: Command) -> Vec<Input> {
+        let mut inputs: Vec<_> = self
+            .table
+            .iter()
+            .filter(|x| *x.1 == command)
+            .map(|x| *x.0)
+            .collect();
+        inputs.sort_by_key(|x| get_input_name(*x));
+        inputs
+    }
+
+    // the inputs move over from whatever they ran before
+    pub fn set(&mut self, command: Command, inputs: &[Input]) {
+        self.table.retain(|_, x| *x != command);
+        for input in inputs {
+            self.table.insert(*input, command);
+        }
+    }
+
+    pub fn reset(&mut self, command: Command) {
+        let defaults: Vec<_> = Bindings::default().get_inputs(command);
+        self.set(command, &defaults);
+    }
+
+    // for the left handed, whatever the left and right buttons do trade places
+    pub fn swap_mouse_buttons(&mut self) {
+        let left = self.table.remove(&Input::Mouse(MouseButton::Left));
+        let right = self.table.remove(&Input::Mouse(MouseButton::Right));
+
+        if let Some(command) = left {
+            self.table.insert(Input::Mouse(MouseButton::Right), command);
+        }
+        if let Some(command) = right {
+            self.table.insert(Input::Mouse(MouseButton::Left), command);
+        }
+    }
+
+    // the commands that differ from the defaults, in the config's format
+    pub fn get_config(&self, left_handed: bool) -> BTreeMap<String, String> {
+        let mut defaults = Bindings::default();
+        if left_handed {
+            defaults.swap_mouse_buttons();
+        }
+
+        COMMANDS
+            .iter()
+            .filter(|x| self.get_inputs(x.1) != defaults.get_inputs(x.1))
+            .map(|&(name, command)| {
+                let inputs: Vec<_> = self
+                    .get_inputs(command)
+                    .into_iter()
+                    .map(get_input_name)
+                    .collect();
+                (name.to_owned(), inputs.join(" "))
+            })
+            .collect()
+    }
+}
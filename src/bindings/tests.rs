@@ -0,0 +1,95 @@
+use super::*;
+
+#[test]
+fn test_input_names() {
+    assert_eq!(parse_input("a"), Some(Input::Key(Key::A)));
+    assert_eq!(parse_input("7"), Some(Input::Key(Key::D7)));
+    assert_eq!(parse_input("f5"), Some(Input::Key(Key::F5)));
+    assert_eq!(parse_input("pageup"), Some(Input::Key(Key::PageUp)));
+    assert_eq!(parse_input("Enter"), Some(Input::Key(Key::Return)));
+    assert_eq!(parse_input("MouseX1"), Some(Input::Mouse(MouseButton::X1)));
+    assert_eq!(parse_input("Key1073741882"), Some(Input::Key(Key::F1)));
+    assert_eq!(parse_input("F13"), None);
+    assert_eq!(parse_input("Spacebar"), None);
+    assert_eq!(parse_input(""), None);
+
+    // every name written reads back as the same input
+    let inputs = DEFAULT_BINDINGS
+        .iter()
+        .map(|x| x.0)
+        .chain(MOUSE_NAMES.iter().map(|x| Input::Mouse(x.1)))
+        .chain(KEY_NAMES.iter().map(|x| Input::Key(x.1)))
+        .chain(vec![Input::Key(Key::F12), Input::Key(Key::Semicolon)]);
+    for input in inputs {
+        assert_eq!(parse_input(&get_input_name(input)), Some(input));
+    }
+
+    assert_eq!(get_input_name(Input::Key(Key::Q)), "Q");
+    assert_eq!(get_input_name(Input::Key(Key::F12)), "F12");
+    assert_eq!(get_input_name(Input::Mouse(MouseButton::Left)), "MouseLeft");
+}
+
+#[test]
+fn test_from_config() {
+    let mut keys = BTreeMap::new();
+    keys.insert("chord".to_owned(), "MouseLeft Space".to_owned());
+    keys.insert("undo".to_owned(), "Z Nope".to_owned());
+    keys.insert("fly".to_owned(), "X".to_owned());
+    let bindings = Bindings::from_config(&keys, false);
+
+    // the inputs move over from what they ran before, and replace every default of the command
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Left)), Some(Command::Chord));
+    assert_eq!(bindings.get(Input::Key(Key::Space)), Some(Command::Chord));
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Middle)), None);
+    assert_eq!(bindings.get(Input::Key(Key::D)), None);
+    assert_eq!(
+        bindings.get_inputs(Command::Reveal),
+        vec![Input::Key(Key::Return)]
+    );
+
+    // unknown inputs are left out and unknown actions skipped
+    assert_eq!(bindings.get_inputs(Command::Undo), vec![Input::Key(Key::Z)]);
+    assert_eq!(bindings.get(Input::Key(Key::X)), None);
+
+    assert_eq!(Bindings::from_config(&BTreeMap::new(), false), Bindings::default());
+}
+
+#[test]
+fn test_swap_mouse_buttons() {
+    let mut bindings = Bindings::default();
+    bindings.swap_mouse_buttons();
+
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Left)), Some(Command::Flag));
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Right)), Some(Command::Reveal));
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Middle)), Some(Command::Chord));
+    assert_eq!(Bindings::from_config(&BTreeMap::new(), true), bindings);
+
+    bindings.swap_mouse_buttons();
+    assert_eq!(bindings, Bindings::default());
+
+    // a button bound to nothing takes nothing over
+    bindings.set(Command::Flag, &[Input::Key(Key::F)]);
+    bindings.swap_mouse_buttons();
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Left)), None);
+    assert_eq!(bindings.get(Input::Mouse(MouseButton::Right)), Some(Command::Reveal));
+}
+
+#[test]
+fn test_get_config() {
+    assert!(Bindings::default().get_config(false).is_empty());
+
+    let mut bindings = Bindings::default();
+    bindings.set(Command::Pause, &[Input::Key(Key::Space), Input::Key(Key::F2)]);
+    let config = bindings.get_config(false);
+
+    // space moved over from reveal, so both differ from the defaults
+    assert_eq!(config.len(), 2);
+    assert_eq!(config["pause"], "F2 Space");
+    assert_eq!(config["reveal"], "MouseLeft Return");
+    assert_eq!(Bindings::from_config(&config, false), bindings);
+
+    // the left handed defaults are not written out
+    let bindings = Bindings::from_config(&BTreeMap::new(), true);
+    assert!(bindings.get_config(true).is_empty());
+    assert_eq!(bindings.get_config(false).len(), 2);
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests;
+
+use game::{GameState, MineSweeper, Position, SquareState};
+use solver::{self, Board};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Reveal(Position),
+    Flag(Position),
+    Chord(Position),
+}
+
+impl Action {
+    pub fn get_position(&self) -> Position {
+        match *self {
+            Action::Reveal(pos) | Action::Flag(pos) | Action::Chord(pos) => pos,
+        }
+    }
+
+    // plays the move the way a click would, the position must be on the board
+    pub fn apply(&self, game: &mut MineSweeper) {
+        match *self {
+            Action::Reveal(pos) => {
+                game.reveal_square(&pos);
+                game.first_moved();
+            }
+            Action::Flag(pos) => game.toggle_flag_square(&pos),
+            Action::Chord(pos) => game.try_reveal_adjacent(&pos),
+        }
+
+        game.update_game_state();
+    }
+}
+
+// anything that can play, given the board as a player would see it
+pub trait Player {
+    fn name(&self) -> &str;
+
+    fn next_action(&mut self, board: &Board, num_mines: u32) -> Action;
+}
+
+// reference bot, plays every proven move and otherwise takes the safest guess
+pub struct SolverBot;
+
+impl Player for SolverBot {
+    fn name(&self) -> &str {
+        "solver"
+    }
+
+    fn next_action(&mut self, board: &Board, num_mines: u32) -> Action {
+        let deductions = solver::find_deductions(board, num_mines);
+
+        if let Some(safe) = deductions.iter().find(|x| !x.is_mine) {
+            Action::Reveal(safe.pos)
+        } else if let Some(mine) = deductions.first() {
+            Action::Flag(mine.pos)
+        } else {
+            let (pos, _) = solver::find_safest_guess(board, num_mines)
+                .expect("ongoing game without covered squares");
+            Action::Reveal(pos)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GameResult {
+    pub won: bool,
+    pub time_ms: f64,
+    pub guesses: u32,
+    pub bbbv: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Report {
+    pub games: u32,
+    pub wins: u32,
+    pub guesses: u32,
+    // sums over won games only
+    pub won_time_ms: f64,
+    pub won_bbbv_per_sec: f64,
+}
+
+impl Report {
+    pub fn add(&mut self, result: &GameResult) {
+        self.games += 1;
+        self.guesses += result.guesses;
+
+        if result.won {
+            self.wins += 1;
+            self.won_time_ms += result.time_ms;
+            self.won_bbbv_per_sec += f64::from(result.bbbv) * 1000.0 / result.time_ms.max(0.001);
+        }
+    }
+
+    pub fn get_win_rate(&self) -> f64 {
+        f64::from(self.wins) / f64::from(self.games.max(1))
+    }
+
+    pub fn get_average_time_ms(&self) -> f64 {
+        self.won_time_ms / f64::from(self.wins.max(1))
+    }
+
+    pub fn get_average_guesses(&self) -> f64 {
+        f64::from(self.guesses) / f64::from(self.games.max(1))
+    }
+
+    pub fn get_average_bbbv_per_sec(&self) -> f64 {
+        self.won_bbbv_per_sec / f64::from(self.wins.max(1))
+    }
+}
+
+// a reveal counts as a guess unless the square was provably safe, the first click is free
+fn is_guess(board: &Board, num_mines: u32, pos: &Position) -> bool {
+    let deductions = solver::find_deductions(board, num_mines);
+    if deductions.iter().any(|x| x.pos == *pos && !x.is_mine) {
+        return false;
+    }
+
+    solver::get_probabilities(board, num_mines)
+        .get(pos)
+        .is_some_and(|p| *p > 0.0)
+}
+
+// plays until the game ends, a player making no progress or an illegal move forfeits
+pub fn play_game<P: Player + ?Sized>(player: &mut P, game: &mut MineSweeper) -> GameResult {
+    let max_moves = game.rows * game.cols * 4;
+    let mut first_reveal = true;
+    let mut guesses = 0;
+
+    for _ in 0..max_moves {
+        if game.state != GameState::Ongoing {
+            break;
+        }
+
+        let board = game.get_visible_board();
+        let action = player.next_action(&board, game.num_mines);
+        let pos = action.get_position();
+
+        if game.check_move(&pos).is_err() {
+            break;
+        }
+
+        if let Action::Reveal(pos) = action {
+            let covered = game.get_square(pos.0, pos.1).state == SquareState::Covered;
+            if covered && !first_reveal && is_guess(&board, game.num_mines, &pos) {
+                guesses += 1;
+            }
+
+            first_reveal = first_reveal && !covered;
+        }
+
+        action.apply(game);
+    }
+
+    GameResult {
+        won: game.state == GameState::Won,
+        // timed on the game's own clock, from the first reveal like a player's game
+        time_ms: game.elapsed_ms() as f64,
+        guesses,
+        bbbv: game.get_3bv(),
+    }
+}
+
+pub fn run_tournament<P: Player + ?Sized>(
+    player: &mut P,
+    cols: u32,
+    rows: u32,
+    num_mines: u32,
+    games: u32,
+) -> Report {
+    let mut report = Report::default();
+    let mut game = MineSweeper::new(cols, rows, num_mines);
+
+    for _ in 0..games {
+        game.reset();
+        report.add(&play_game(player, &mut game));
+    }
+
+    report
+}
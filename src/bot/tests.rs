@@ -0,0 +1,102 @@
+use super::*;
+
+use game::clock::ManualClock;
+use game::Rules;
+
+// always clicks the top left corner, so it stalls right after the first move
+struct StuckPlayer;
+
+impl Player for StuckPlayer {
+    fn name(&self) -> &str {
+        "stuck"
+    }
+
+    fn next_action(&mut self, _board: &Board, _num_mines: u32) -> Action {
+        Action::Reveal(Position(0, 0))
+    }
+}
+
+// the solver taking a quarter of a second over every move
+struct SlowPlayer {
+    clock: ManualClock,
+    moves: u32,
+}
+
+impl Player for SlowPlayer {
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn next_action(&mut self, board: &Board, num_mines: u32) -> Action {
+        self.clock.advance(250);
+        self.moves += 1;
+        SolverBot.next_action(board, num_mines)
+    }
+}
+
+#[test]
+fn test_report() {
+    let mut report = Report::default();
+
+    report.add(&GameResult {
+        won: true,
+        time_ms: 2000.0,
+        guesses: 1,
+        bbbv: 10,
+    });
+    report.add(&GameResult {
+        won: false,
+        time_ms: 500.0,
+        guesses: 3,
+        bbbv: 20,
+    });
+
+    assert_eq!(report.games, 2);
+    assert_eq!(report.wins, 1);
+    assert!((report.get_win_rate() - 0.5).abs() < 1e-9);
+    assert!((report.get_average_time_ms() - 2000.0).abs() < 1e-9);
+    assert!((report.get_average_guesses() - 2.0).abs() < 1e-9);
+    assert!((report.get_average_bbbv_per_sec() - 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_stuck_player_forfeits() {
+    let report = run_tournament(&mut StuckPlayer, 9, 9, 10, 3);
+
+    assert_eq!(report.games, 3);
+    assert_eq!(report.wins, 0);
+    assert_eq!(report.guesses, 0);
+}
+
+#[test]
+fn test_solver_bot() {
+    let mut bot = SolverBot;
+    let mut report = Report::default();
+
+    // the same beginner boards every run, which rarely need a guess
+    for seed in 0..20 {
+        let mut game = MineSweeper::with_seed(9, 9, 10, Rules::default(), seed);
+        report.add(&play_game(&mut bot, &mut game));
+    }
+
+    assert_eq!(report.games, 20);
+    // a better solver may win more of them, never fewer
+    assert!(report.wins >= 18);
+}
+
+#[test]
+fn test_game_clock() {
+    let clock = ManualClock::new();
+    let mut player = SlowPlayer {
+        clock: clock.clone(),
+        moves: 0,
+    };
+    let mut game = MineSweeper::with_seed(9, 9, 10, Rules::default(), 0);
+    game.set_clock(Box::new(clock));
+
+    let result = play_game(&mut player, &mut game);
+
+    // the clock starts with the first reveal
+    assert!(result.won);
+    assert_eq!(result.time_ms, f64::from(player.moves - 1) * 250.0);
+}
@@ -0,0 +1,385 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use dirs;
+use toml;
+
+use game::{Rules, MAX_TIME};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CustomBoard {
+    pub cols: u32,
+    pub rows: u32,
+    pub mines: u32,
+}
+
+impl Default for CustomBoard {
+    fn default() -> CustomBoard {
+        CustomBoard {
+            cols: 12,
+            rows: 15,
+            mines: 13,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PresetConfig {
+    pub name: String,
+    pub cols: u32,
+    pub rows: u32,
+    pub mines: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RulesConfig {
+    pub lives: u32,
+    pub time_limit: Option<u64>,
+    pub bonus_seconds: u64,
+}
+
+impl Default for RulesConfig {
+    fn default() -> RulesConfig {
+        RulesConfig::from(Rules::default())
+    }
+}
+
+impl From<Rules> for RulesConfig {
+    fn from(rules: Rules) -> RulesConfig {
+        RulesConfig {
+            lives: rules.lives,
+            time_limit: rules.time_limit,
+            bonus_seconds: rules.bonus_seconds,
+        }
+    }
+}
+
+impl RulesConfig {
+    pub fn get_rules(&self) -> Rules {
+        Rules {
+            lives: self.lives.max(1),
+            time_limit: self.time_limit.map(|limit| limit.min(MAX_TIME)),
+            bonus_seconds: self.bonus_seconds,
+            ..Rules::default()
+        }
+    }
+}
+
+// colors as [red, green, blue, alpha] from 0 to 1, anything left out keeps the built-in color
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub background: Option<[f32; 4]>,
+    pub ui_rect: Option<[f32; 4]>,
+    pub ui_text: Option<[f32; 4]>,
+    pub cell_covered: Option<[f32; 4]>,
+    pub cell_revealed: Option<[f32; 4]>,
+    pub cell_border: Option<[f32; 4]>,
+    pub mine_revealed: Option<[f32; 4]>,
+    pub mine_border: Option<[f32; 4]>,
+    // edges of the raised cells in skins with a bevel
+    pub bevel_light: Option<[f32; 4]>,
+    pub bevel_shadow: Option<[f32; 4]>,
+    // colors of the numbers 1 to 8, a shorter list keeps the rest
+    pub numbers: Option<Vec<[f32; 4]>>,
+}
+
+// everything is optional in the file, missing sections fall back to the defaults
+//
+//   preset_set = "modern"
+//   skin = "Dark"
+//   palette = "Okabe-Ito"
+//   high_contrast = true
+//   patterns = true
+//   zoom = 1.5
+//   left_handed = true
+//
+//   [custom]
+//   cols = 30
+//   rows = 16
+//   mines = 99
+//
+//   [rules]
+//   lives = 3
+//
+//   [keys]
+//   reset = "N"
+//   chord = "MouseLeft Space"
+//
+//   [theme]
+//   background = [0.2, 0.2, 0.2, 1.0]
+//
+//   [[presets]]
+//   name = "Tiny"
+//   cols = 5
+//   rows = 5
+//   mines = 3
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // classic or modern, the built-in presets the others are added to
+    pub preset_set: Option<String>,
+    // name of the skin found under assets/skins, the built-in look when left out
+    pub skin: Option<String>,
+    // number and mine colors, Default, Okabe-Ito or Tol, layered over the skin and theme
+    pub palette: Option<String>,
+    pub high_contrast: bool,
+    // shapes on flagged and exploded cells so they do not rely on color alone
+    pub patterns: bool,
+    // window size as a multiple of the board's
+    pub zoom: Option<f64>,
+    // the left and right mouse buttons trade places
+    pub left_handed: bool,
+    pub custom: CustomBoard,
+    pub rules: RulesConfig,
+    // action name to the keys and mouse buttons that run it, such as chord = "MouseLeft Space"
+    pub keys: BTreeMap<String, String>,
+    pub theme: ThemeConfig,
+    pub presets: Vec<PresetConfig>,
+}
+
+fn invalid_data<E: ToString>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+impl Config {
+    pub fn get_default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("minesweep-rs").join("config.toml"))
+    }
+
+    // a missing file is the default config
+    pub fn load(path: &Path) -> io::Result<Config> {
+        let mut contents = String::new();
+
+        match File::open(path) {
+            Ok(mut file) => file.read_to_string(&mut contents)?,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(err) => return Err(err),
+        };
+
+        Config::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> io::Result<Config> {
+        toml::from_str(contents).map_err(invalid_data)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents = toml::to_string(self).map_err(invalid_data)?;
+        File::create(path)?.write_all(contents.as_bytes())
+    }
+
+    // edits the file at path as text, so everything else in it and its comments stay as they
+    // were, nothing is written unless the file reads before and the result passes the check
+    fn update_in_place<E, C>(path: &Path, what: &str, edit: E, check: C) -> io::Result<()>
+    where
+        E: FnOnce(&str, &Config) -> String,
+        C: FnOnce(&Config) -> bool,
+    {
+        let mut contents = String::new();
+        match File::open(path) {
+            Ok(mut file) => {
+                file.read_to_string(&mut contents)?;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        };
+
+        let contents = edit(&contents, &Config::parse(&contents)?);
+        if !check(&Config::parse(&contents)?) {
+            return Err(invalid_data(format!(
+                "{} could not be updated in place",
+                what
+            )));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        File::create(path)?.write_all(contents.as_bytes())
+    }
+
+    // only the [custom] table's keys are written
+    pub fn save_custom(path: &Path, custom: &CustomBoard) -> io::Result<()> {
+        Config::update_in_place(
+            path,
+            "[custom]",
+            |contents, _| {
+                set_table_keys(
+                    contents,
+                    Some("custom"),
+                    &[
+                        ("cols", Some(custom.cols.to_string())),
+                        ("rows", Some(custom.rows.to_string())),
+                        ("mines", Some(custom.mines.to_string())),
+                    ],
+                )
+            },
+            |config| config.custom == *custom,
+        )
+    }
+
+    // only the [keys] table and left_handed are written, bindings back to their defaults are
+    // taken out of the table
+    pub fn save_keys(
+        path: &Path,
+        keys: &BTreeMap<String, String>,
+        left_handed: bool,
+    ) -> io::Result<()> {
+        Config::update_in_place(
+            path,
+            "[keys]",
+            |contents, config| {
+                let mut values: Vec<_> = config
+                    .keys
+                    .keys()
+                    .filter(|x| !keys.contains_key(*x))
+                    .map(|x| (&x[..], None))
+                    .collect();
+                values.extend(
+                    keys.iter()
+                        .map(|(name, inputs)| (&name[..], Some(get_string_value(inputs)))),
+                );
+
+                let contents = set_table_keys(contents, Some("keys"), &values);
+                set_table_keys(
+                    &contents,
+                    None,
+                    &[("left_handed", Some(left_handed.to_string()))],
+                )
+            },
+            |config| config.keys == *keys && config.left_handed == left_handed,
+        )
+    }
+
+    // only palette, high_contrast and patterns are written, the default palette takes palette out
+    pub fn save_colors(
+        path: &Path,
+        palette: Option<&str>,
+        high_contrast: bool,
+        patterns: bool,
+    ) -> io::Result<()> {
+        Config::update_in_place(
+            path,
+            "palette",
+            |contents, _| {
+                set_table_keys(
+                    contents,
+                    None,
+                    &[
+                        ("palette", palette.map(get_string_value)),
+                        ("high_contrast", Some(high_contrast.to_string())),
+                        ("patterns", Some(patterns.to_string())),
+                    ],
+                )
+            },
+            |config| {
+                config.palette.as_ref().map(|x| &x[..]) == palette
+                    && config.high_contrast == high_contrast
+                    && config.patterns == patterns
+            },
+        )
+    }
+
+    pub fn get_preset(&self, name: &str) -> Option<&PresetConfig> {
+        self.presets
+            .iter()
+            .find(|x| x.name.eq_ignore_ascii_case(name))
+    }
+}
+
+// the key a line sets, None for comments, blank lines and table headers
+fn get_line_key(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if line.starts_with('#') || line.starts_with('[') {
+        return None;
+    }
+
+    line.split_once('=').map(|x| x.0.trim())
+}
+
+// a string as written in the file, quoted and escaped
+fn get_string_value(value: &str) -> String {
+    toml::Value::String(value.to_owned()).to_string()
+}
+
+// the text with the keys of the table set to the values, or the keys above the first table for
+// None, replacing lines that set them and adding the rest right under the table's header, or in a
+// new table at the end of the text, keys without a value have their lines taken out
+fn set_table_keys(
+    contents: &str,
+    table: Option<&str>,
+    values: &[(&str, Option<String>)],
+) -> String {
+    let mut lines: Vec<String> = contents.lines().map(|x| x.to_owned()).collect();
+    let start = match table {
+        Some(table) => {
+            let header = format!("[{}]", table);
+            let start = lines
+                .iter()
+                .position(|x| x.split('#').next().unwrap().trim() == header);
+
+            match start {
+                Some(start) => start + 1,
+                None => {
+                    if lines.last().is_some_and(|x| !x.trim().is_empty()) {
+                        lines.push(String::new());
+                    }
+                    lines.push(header);
+                    lines.len()
+                }
+            }
+        }
+        None => 0,
+    };
+    let end = lines[start..]
+        .iter()
+        .position(|x| x.trim_start().starts_with('['))
+        .map_or(lines.len(), |x| start + x);
+
+    let mut body = Vec::new();
+    let mut missing: Vec<_> = values.iter().filter(|x| x.1.is_some()).collect();
+    for line in lines.drain(start..end) {
+        match values.iter().find(|x| get_line_key(&line) == Some(x.0)) {
+            Some(&(key, Some(ref value))) => {
+                body.push(format!("{} = {}", key, value));
+                missing.retain(|x| x.0 != key);
+            }
+            Some(&(_, None)) => (),
+            None => body.push(line),
+        }
+    }
+
+    let missing: Vec<_> = missing
+        .into_iter()
+        .map(|&(key, ref value)| format!("{} = {}", key, value.as_ref().unwrap()))
+        .collect();
+    // above the first table they go after whatever is already there, so not before the comments
+    // the file starts with, and apart from the table
+    let at = match table {
+        Some(_) => 0,
+        None => body
+            .iter()
+            .rposition(|x| !x.trim().is_empty())
+            .map_or(0, |x| x + 1),
+    };
+    if table.is_none() && at == 0 && !missing.is_empty() && !lines.is_empty() {
+        body.insert(0, String::new());
+    }
+    body.splice(at..at, missing);
+    lines.splice(start..start, body);
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
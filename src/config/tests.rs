@@ -0,0 +1,233 @@
+use super::*;
+
+use std::env;
+
+#[test]
+fn test_parse() {
+    let config = Config::parse(
+        r#"
+preset_set = "modern"
+zoom = 1.5
+left_handed = true
+palette = "Tol"
+patterns = true
+
+[custom]
+cols = 30
+rows = 16
+mines = 99
+
+[rules]
+lives = 3
+
+[keys]
+reset = "N"
+chord = "MouseLeft Space"
+
+[theme]
+background = [0.2, 0.2, 0.2, 1.0]
+
+[[presets]]
+name = "Tiny"
+cols = 5
+rows = 5
+mines = 3
+"#,
+    ).unwrap();
+
+    assert_eq!(
+        config.custom,
+        CustomBoard {
+            cols: 30,
+            rows: 16,
+            mines: 99,
+        }
+    );
+    assert_eq!(config.preset_set, Some("modern".to_owned()));
+    assert_eq!(config.zoom, Some(1.5));
+    assert_eq!(config.skin, None);
+    assert!(config.left_handed);
+    assert_eq!(config.palette, Some("Tol".to_owned()));
+    assert!(!config.high_contrast);
+    assert!(config.patterns);
+    assert_eq!(config.rules.get_rules().lives, 3);
+    assert_eq!(config.rules.get_rules().time_limit, None);
+
+    // a countdown longer than the timer shows is cut down to fit
+    let mut rules = config.rules;
+    rules.time_limit = Some(20_000);
+    assert_eq!(rules.get_rules().time_limit, Some(MAX_TIME));
+    assert_eq!(config.keys["reset"], "N");
+    assert_eq!(config.keys["chord"], "MouseLeft Space");
+    assert_eq!(config.theme.background, Some([0.2, 0.2, 0.2, 1.0]));
+    assert_eq!(config.theme.ui_text, None);
+    assert_eq!(config.get_preset("tiny").unwrap().mines, 3);
+    assert!(config.get_preset("huge").is_none());
+
+    assert_eq!(Config::parse("").unwrap(), Config::default());
+    assert!(Config::parse("[custom]\ncols = \"wide\"").is_err());
+}
+
+#[test]
+fn test_save_load() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-config-{}", ::std::process::id()))
+        .join("config.toml");
+
+    assert_eq!(Config::load(&path).unwrap(), Config::default());
+
+    let mut config = Config {
+        preset_set: Some("classic".to_owned()),
+        left_handed: true,
+        palette: Some("Okabe-Ito".to_owned()),
+        high_contrast: true,
+        ..Config::default()
+    };
+    config.custom.cols = 40;
+    config.rules.time_limit = Some(60);
+    config.keys.insert("pause".to_owned(), "Space".to_owned());
+    config.presets.push(PresetConfig {
+        name: "Wide".to_owned(),
+        cols: 40,
+        rows: 10,
+        mines: 50,
+    });
+    config.save(&path).unwrap();
+
+    assert_eq!(Config::load(&path).unwrap(), config);
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_set_table_keys() {
+    let custom = [
+        ("cols", Some("40".to_owned())),
+        ("rows", Some("10".to_owned())),
+        ("mines", Some("50".to_owned())),
+    ];
+
+    // the other keys, tables and comments are left alone
+    let contents = "# mine\nzoom = 2.0\n\n[custom]\n# last played\ncols = 30\nmines = 99\n\n\
+                    [rules]\nlives = 3\n";
+    assert_eq!(
+        set_table_keys(contents, Some("custom"), &custom),
+        "# mine\nzoom = 2.0\n\n[custom]\nrows = 10\n# last played\ncols = 40\nmines = 50\n\n\
+         [rules]\nlives = 3\n"
+    );
+
+    assert_eq!(
+        set_table_keys("zoom = 2.0", Some("custom"), &custom),
+        "zoom = 2.0\n\n[custom]\ncols = 40\nrows = 10\nmines = 50\n"
+    );
+    assert_eq!(
+        set_table_keys("", Some("custom"), &custom),
+        "[custom]\ncols = 40\nrows = 10\nmines = 50\n"
+    );
+
+    // keys without a value go
+    let keys = [("reset", None), ("pause", Some("\"P\"".to_owned()))];
+    assert_eq!(
+        set_table_keys("[keys]\n# mine\nreset = \"N\"\n", Some("keys"), &keys),
+        "[keys]\npause = \"P\"\n# mine\n"
+    );
+
+    // above the first table new keys go after the ones there, and apart from a table right at
+    // the start
+    let left_handed = [("left_handed", Some("true".to_owned()))];
+    assert_eq!(
+        set_table_keys("# mine\nzoom = 2.0\n\n[custom]\ncols = 30\n", None, &left_handed),
+        "# mine\nzoom = 2.0\nleft_handed = true\n\n[custom]\ncols = 30\n"
+    );
+    assert_eq!(
+        set_table_keys("[custom]\ncols = 30\n", None, &left_handed),
+        "left_handed = true\n\n[custom]\ncols = 30\n"
+    );
+    let contents = "left_handed = false # swapped\n[keys]\nleft_handed = 1\n";
+    assert_eq!(
+        set_table_keys(contents, None, &left_handed),
+        "left_handed = true\n[keys]\nleft_handed = 1\n"
+    );
+}
+
+#[test]
+fn test_save_custom() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-custom-{}", ::std::process::id()))
+        .join("config.toml");
+    let custom = CustomBoard {
+        cols: 40,
+        rows: 10,
+        mines: 50,
+    };
+
+    Config::save_custom(&path, &custom).unwrap();
+    assert_eq!(Config::load(&path).unwrap().custom, custom);
+
+    // a file that does not read is not written over
+    fs::write(&path, "[custom\ncols = 30\n").unwrap();
+    assert!(Config::save_custom(&path, &custom).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "[custom\ncols = 30\n");
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_save_keys() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-keys-{}", ::std::process::id()))
+        .join("config.toml");
+    let contents = "# my setup\nzoom = 2.0 # big\n\n[keys]\n# not the default\nreset = \"N\"\n\
+                    undo = \"Z\"\n\n[rules]\nlives = 3\n";
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, contents).unwrap();
+
+    // undo rebound, reset back to its default
+    let mut keys = BTreeMap::new();
+    keys.insert("undo".to_owned(), "U MouseMiddle".to_owned());
+    Config::save_keys(&path, &keys, true).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "# my setup\nzoom = 2.0 # big\nleft_handed = true\n\n[keys]\n# not the default\n\
+         undo = \"U MouseMiddle\"\n\n[rules]\nlives = 3\n"
+    );
+    let config = Config::load(&path).unwrap();
+    assert_eq!(config.keys, keys);
+    assert!(config.left_handed);
+
+    // a file that does not read is not written over
+    fs::write(&path, "[keys\n").unwrap();
+    assert!(Config::save_keys(&path, &keys, false).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "[keys\n");
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_save_colors() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-colors-{}", ::std::process::id()))
+        .join("config.toml");
+    let contents =
+        "# my setup\npalette = \"Tol\" # for me\nhigh_contrast = true\n\n[rules]\nlives = 3\n";
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, contents).unwrap();
+
+    Config::save_colors(&path, Some("Okabe-Ito"), false, true).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "# my setup\npalette = \"Okabe-Ito\"\nhigh_contrast = false\npatterns = true\n\n\
+         [rules]\nlives = 3\n"
+    );
+
+    // back to the default palette
+    Config::save_colors(&path, None, false, true).unwrap();
+    let config = Config::load(&path).unwrap();
+    assert_eq!(config.palette, None);
+    assert!(!config.high_contrast);
+    assert!(config.patterns);
+    assert_eq!(config.rules.lives, 3);
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rand::random;
+
+use bot::Action;
+use game::{GameState, MineSweeper, Position, Rules};
+
+// line based protocol for one board shared by everyone, the host orders all moves
+//
+//   host -> client     welcome <id>
+//   host -> client     board <cols> <rows> <mines> <seed>, then every move made so far
+//   client -> host     reveal|flag|chord <row> <col>
+//   host -> all        move <id> reveal|flag|chord <row> <col>
+//   client -> host     cursor <row> <col>
+//   host -> all        cursor <id> <row> <col>
+//   client -> host     reset, only taken once the game is over
+//   host -> all        board <cols> <rows> <mines> <seed>
+//   host -> all        left <id>
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CoopBoard {
+    pub cols: u32,
+    pub rows: u32,
+    pub num_mines: u32,
+    pub seed: u64,
+}
+
+impl CoopBoard {
+    // classic rules, so a single mine hit ends the game for the whole team
+    pub fn new_game(&self) -> MineSweeper {
+        MineSweeper::with_seed(
+            self.cols,
+            self.rows,
+            self.num_mines,
+            Rules::default(),
+            self.seed,
+        )
+    }
+
+    fn get_line(&self) -> String {
+        format!(
+            "board {} {} {} {}",
+            self.cols, self.rows, self.num_mines, self.seed
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoopEvent {
+    Board(CoopBoard),
+    Move(u32, Action),
+    Cursor(u32, Position),
+    Left(u32),
+}
+
+fn get_action_text(action: &Action) -> String {
+    let (name, pos) = match *action {
+        Action::Reveal(pos) => ("reveal", pos),
+        Action::Flag(pos) => ("flag", pos),
+        Action::Chord(pos) => ("chord", pos),
+    };
+
+    format!("{} {} {}", name, pos.0, pos.1)
+}
+
+fn parse_position(args: &[&str]) -> Option<Position> {
+    match (args.first()?.parse(), args.get(1)?.parse()) {
+        (Ok(row), Ok(col)) if args.len() == 2 => Some(Position(row, col)),
+        _ => None,
+    }
+}
+
+fn parse_action(words: &[&str]) -> Option<Action> {
+    let pos = parse_position(words.get(1..)?)?;
+
+    match words[0] {
+        "reveal" => Some(Action::Reveal(pos)),
+        "flag" => Some(Action::Flag(pos)),
+        "chord" => Some(Action::Chord(pos)),
+        _ => None,
+    }
+}
+
+fn parse_event(line: &str) -> Option<CoopEvent> {
+    let words: Vec<_> = line.split_whitespace().collect();
+
+    match words.first() {
+        Some(&"board") if words.len() == 5 => Some(CoopEvent::Board(CoopBoard {
+            cols: words[1].parse().ok()?,
+            rows: words[2].parse().ok()?,
+            num_mines: words[3].parse().ok()?,
+            seed: words[4].parse().ok()?,
+        })),
+        Some(&"move") if words.len() == 5 => {
+            Some(CoopEvent::Move(words[1].parse().ok()?, parse_action(&words[2..])?))
+        }
+        Some(&"cursor") if words.len() == 4 => Some(CoopEvent::Cursor(
+            words[1].parse().ok()?,
+            parse_position(&words[2..])?,
+        )),
+        Some(&"left") if words.len() == 2 => Some(CoopEvent::Left(words[1].parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+struct Host {
+    board: CoopBoard,
+    game: MineSweeper,
+    // every move on the current board, replayed to players joining late
+    history: Vec<String>,
+    streams: Vec<(u32, TcpStream)>,
+}
+
+impl Host {
+    fn broadcast(&mut self, line: &str) {
+        // a player that dropped out is cleaned up by its own connection thread
+        for (_, stream) in &mut self.streams {
+            let _ = writeln!(stream, "{}", line);
+        }
+    }
+
+    fn handle_line(&mut self, id: u32, line: &str) {
+        let words: Vec<_> = line.split_whitespace().collect();
+
+        if let Some(action) = parse_action(&words) {
+            // moves arrive one at a time under the lock, whatever comes later plays on the result
+            if self.game.check_move(&action.get_position()).is_err() {
+                return;
+            }
+
+            action.apply(&mut self.game);
+            let line = format!("move {} {}", id, get_action_text(&action));
+            self.history.push(line.clone());
+            self.broadcast(&line);
+        } else if words.first() == Some(&"cursor") {
+            if let Some(pos) = parse_position(&words[1..]) {
+                self.broadcast(&format!("cursor {} {} {}", id, pos.0, pos.1));
+            }
+        } else if words == ["reset"] && self.game.state != GameState::Ongoing {
+            self.board.seed = random();
+            self.game = self.board.new_game();
+            self.history.clear();
+            let line = self.board.get_line();
+            self.broadcast(&line);
+        }
+    }
+}
+
+pub struct CoopServer {
+    listener: TcpListener,
+    host: Arc<Mutex<Host>>,
+}
+
+impl CoopServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A, board: CoopBoard) -> io::Result<CoopServer> {
+        Ok(CoopServer {
+            listener: TcpListener::bind(addr)?,
+            host: Arc::new(Mutex::new(Host {
+                board,
+                game: board.new_game(),
+                history: Vec::new(),
+                streams: Vec::new(),
+            })),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // accepts players until the listener fails, each one is served on its own thread
+    pub fn run(&self) -> io::Result<()> {
+        for (id, stream) in self.listener.incoming().enumerate() {
+            let stream = stream?;
+            let host = self.host.clone();
+
+            thread::spawn(move || {
+                let _ = CoopServer::serve_player(id as u32, stream, &host);
+
+                let mut host = host.lock().unwrap();
+                host.streams.retain(|&(x, _)| x != id as u32);
+                host.broadcast(&format!("left {}", id));
+            });
+        }
+
+        Ok(())
+    }
+
+    fn serve_player(id: u32, stream: TcpStream, host: &Mutex<Host>) -> io::Result<()> {
+        let mut writer = stream.try_clone()?;
+
+        {
+            let mut host = host.lock().unwrap();
+            writeln!(writer, "welcome {}", id)?;
+            writeln!(writer, "{}", host.board.get_line())?;
+            for line in &host.history {
+                writeln!(writer, "{}", line)?;
+            }
+            host.streams.push((id, writer));
+        }
+
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            host.lock().unwrap().handle_line(id, &line);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CoopClient {
+    pub id: u32,
+    // the board when joining, later boards come in as events
+    pub board: CoopBoard,
+    stream: TcpStream,
+    events: Receiver<CoopEvent>,
+}
+
+impl CoopClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<CoopClient> {
+        let stream = TcpStream::connect(addr)?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut welcome = String::new();
+        reader.read_line(&mut welcome)?;
+
+        let id = match welcome.split_whitespace().collect::<Vec<_>>()[..] {
+            ["welcome", id] => id.parse().map_err(|_| invalid_data("bad welcome"))?,
+            _ => return Err(invalid_data("expected welcome")),
+        };
+
+        let mut board = String::new();
+        reader.read_line(&mut board)?;
+        let board = match parse_event(&board) {
+            Some(CoopEvent::Board(board)) => board,
+            _ => return Err(invalid_data("expected board")),
+        };
+
+        let (sender, events) = channel();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                if let Some(event) = parse_event(&line) {
+                    if sender.send(event).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(CoopClient {
+            id,
+            board,
+            stream,
+            events,
+        })
+    }
+
+    // moves only take effect once the host sends them back as events
+    pub fn send_action(&mut self, action: &Action) -> io::Result<()> {
+        writeln!(self.stream, "{}", get_action_text(action))
+    }
+
+    pub fn send_cursor(&mut self, pos: &Position) -> io::Result<()> {
+        writeln!(self.stream, "cursor {} {}", pos.0, pos.1)
+    }
+
+    pub fn send_reset(&mut self) -> io::Result<()> {
+        writeln!(self.stream, "reset")
+    }
+
+    // everything received since the last call, in the order the host sent it
+    pub fn poll_events(&self) -> Vec<CoopEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for CoopClient {
+    fn drop(&mut self) {
+        // the reader thread holds its own handle, so the socket has to be closed explicitly
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
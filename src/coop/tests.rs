@@ -0,0 +1,82 @@
+use super::*;
+
+use std::time::{Duration, Instant};
+
+fn wait_for_events(client: &CoopClient, count: usize) -> Vec<CoopEvent> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut events = Vec::new();
+
+    while events.len() < count {
+        assert!(Instant::now() < deadline, "host never sent the events");
+        events.extend(client.poll_events());
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    events
+}
+
+#[test]
+fn test_parse_event() {
+    assert_eq!(
+        parse_event("move 2 chord 3 4"),
+        Some(CoopEvent::Move(2, Action::Chord(Position(3, 4))))
+    );
+    assert_eq!(
+        parse_event("cursor 1 0 7"),
+        Some(CoopEvent::Cursor(1, Position(0, 7)))
+    );
+    assert_eq!(parse_event("left 5"), Some(CoopEvent::Left(5)));
+    assert_eq!(parse_event("move 2 dig 3 4"), None);
+    assert_eq!(parse_event("cursor 1 0"), None);
+}
+
+#[test]
+fn test_shared_board() {
+    let board = CoopBoard {
+        cols: 9,
+        rows: 9,
+        num_mines: 10,
+        seed: 7,
+    };
+    let server = CoopServer::bind("127.0.0.1:0", board).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut alice = CoopClient::connect(addr).unwrap();
+    assert_eq!(alice.board, board);
+
+    let mut alice_game = alice.board.new_game();
+    alice.send_action(&Action::Reveal(Position(4, 4))).unwrap();
+    // off the board, the host drops it instead of passing it on
+    alice.send_action(&Action::Flag(Position(9, 0))).unwrap();
+    // the game is still going, so the host ignores this too
+    alice.send_reset().unwrap();
+    alice.send_cursor(&Position(2, 3)).unwrap();
+
+    assert_eq!(
+        wait_for_events(&alice, 2),
+        vec![
+            CoopEvent::Move(alice.id, Action::Reveal(Position(4, 4))),
+            CoopEvent::Cursor(alice.id, Position(2, 3)),
+        ]
+    );
+    Action::Reveal(Position(4, 4)).apply(&mut alice_game);
+
+    // a late joiner gets the board and every move played on it
+    let bob = CoopClient::connect(addr).unwrap();
+    let mut bob_game = bob.board.new_game();
+    for event in wait_for_events(&bob, 1) {
+        match event {
+            CoopEvent::Move(id, action) => {
+                assert_eq!(id, alice.id);
+                action.apply(&mut bob_game);
+            }
+            _ => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    assert_eq!(bob_game.get_visible_board(), alice_game.get_visible_board());
+
+    drop(bob);
+    assert!(wait_for_events(&alice, 1).contains(&CoopEvent::Left(1)));
+}
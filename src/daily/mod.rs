@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, NaiveDate};
+use dirs;
+
+use game::preset::Preset;
+use game::{MineSweeper, Rules};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// the same as the high scores', so a preset renamed in the config is another daily board
+pub fn get_board_key(preset: &Preset) -> String {
+    format!("{} {}x{} {}", preset.name, preset.cols, preset.rows, preset.mines)
+}
+
+// FNV-1a over the date and the board, the same on every machine and every build so everyone
+// gets the same layout without asking anyone
+pub fn get_seed(date: NaiveDate, preset: &Preset) -> u64 {
+    let text = format!("{} {}", date.format(DATE_FORMAT), get_board_key(preset));
+
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, x| {
+        (hash ^ u64::from(x)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// the day's board for the preset, always played by the classic rules
+pub fn new_game(date: NaiveDate, preset: &Preset) -> MineSweeper {
+    MineSweeper::with_seed(
+        preset.cols,
+        preset.rows,
+        preset.mines,
+        Rules::default(),
+        get_seed(date, preset),
+    )
+}
+
+pub fn get_default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("minesweep-rs").join("daily.txt"))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Won,
+    Lost,
+    // started and left before the end, which breaks a streak like a loss
+    Unfinished,
+}
+
+impl Outcome {
+    fn get_name(self) -> &'static str {
+        match self {
+            Outcome::Won => "won",
+            Outcome::Lost => "lost",
+            Outcome::Unfinished => "unfinished",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Outcome> {
+        match name {
+            "won" => Some(Outcome::Won),
+            "lost" => Some(Outcome::Lost),
+            "unfinished" => Some(Outcome::Unfinished),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Attempt {
+    pub outcome: Outcome,
+    // time of the finished game, 0 while unfinished
+    pub time_ms: u64,
+}
+
+// the official attempt at every daily board played, one line each with the board key last
+//
+//   <date>\t<outcome>\t<time ms>\t<board key>
+#[derive(Default)]
+pub struct DailyHistory {
+    attempts: BTreeMap<(String, NaiveDate), Attempt>,
+}
+
+impl DailyHistory {
+    // a missing file is an empty history, unreadable lines are skipped
+    pub fn load(path: &Path) -> io::Result<DailyHistory> {
+        let mut history = DailyHistory::default();
+
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(history),
+            Err(err) => return Err(err),
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let fields: Vec<_> = line.splitn(4, '\t').collect();
+            if fields.len() != 4 {
+                continue;
+            }
+
+            if let (Ok(date), Some(outcome), Ok(time_ms)) = (
+                NaiveDate::parse_from_str(fields[0], DATE_FORMAT),
+                Outcome::parse(fields[1]),
+                fields[2].parse(),
+            ) {
+                history
+                    .attempts
+                    .insert((fields[3].to_owned(), date), Attempt { outcome, time_ms });
+            }
+        }
+
+        Ok(history)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = File::create(path)?;
+        for (&(ref key, date), attempt) in &self.attempts {
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}",
+                date.format(DATE_FORMAT),
+                attempt.outcome.get_name(),
+                attempt.time_ms,
+                key
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str, date: NaiveDate) -> Option<&Attempt> {
+        self.attempts.get(&(key.to_owned(), date))
+    }
+
+    // the first attempt of the day is the official one, false if there already is one
+    pub fn start(&mut self, key: &str, date: NaiveDate) -> bool {
+        if self.get(key, date).is_some() {
+            return false;
+        }
+
+        self.attempts.insert(
+            (key.to_owned(), date),
+            Attempt {
+                outcome: Outcome::Unfinished,
+                time_ms: 0,
+            },
+        );
+        true
+    }
+
+    // the outcome of the official attempt, which only changes while it is unfinished
+    pub fn finish(&mut self, key: &str, date: NaiveDate, outcome: Outcome, time_ms: u64) {
+        if let Some(attempt) = self.attempts.get_mut(&(key.to_owned(), date)) {
+            if attempt.outcome == Outcome::Unfinished {
+                *attempt = Attempt { outcome, time_ms };
+            }
+        }
+    }
+
+    // days in a row won up to today, today only breaks it once lost as it may still be going
+    pub fn get_streak(&self, key: &str, today: NaiveDate) -> u32 {
+        let mut date = match self.get(key, today).map(|x| x.outcome) {
+            Some(Outcome::Won) => today,
+            Some(Outcome::Lost) => return 0,
+            _ => today - Duration::days(1),
+        };
+
+        let mut streak = 0;
+        while self.get(key, date).map(|x| x.outcome) == Some(Outcome::Won) {
+            streak += 1;
+            date -= Duration::days(1);
+        }
+        streak
+    }
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+use std::env;
+
+fn get_date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2018, 6, day).unwrap()
+}
+
+#[test]
+fn test_seed() {
+    let expert = Preset::new("Expert", 30, 16, 99);
+
+    assert_eq!(get_board_key(&expert), "Expert 30x16 99");
+    assert_eq!(get_seed(get_date(1), &expert), get_seed(get_date(1), &expert));
+    assert!(get_seed(get_date(1), &expert) != get_seed(get_date(2), &expert));
+    let other = Preset::new("Expert", 30, 16, 98);
+    assert!(get_seed(get_date(1), &expert) != get_seed(get_date(1), &other));
+
+    let game = new_game(get_date(1), &expert);
+    assert_eq!(game.get_seed(), get_seed(get_date(1), &expert));
+    assert_eq!(game.rules, Rules::default());
+    assert_eq!(game.get_layout(), new_game(get_date(1), &expert).get_layout());
+}
+
+#[test]
+fn test_streak() {
+    let mut history = DailyHistory::default();
+    assert_eq!(history.get_streak("Beginner", get_date(5)), 0);
+
+    for day in 1..5 {
+        assert!(history.start("Beginner", get_date(day)));
+        history.finish("Beginner", get_date(day), Outcome::Won, 1000);
+    }
+
+    // only the first attempt of the day counts
+    assert!(!history.start("Beginner", get_date(4)));
+    history.finish("Beginner", get_date(4), Outcome::Lost, 2000);
+    assert_eq!(history.get("Beginner", get_date(4)).unwrap().outcome, Outcome::Won);
+
+    // today not played yet keeps yesterday's streak
+    assert_eq!(history.get_streak("Beginner", get_date(4)), 4);
+    assert_eq!(history.get_streak("Beginner", get_date(5)), 4);
+    assert_eq!(history.get_streak("Beginner", get_date(6)), 0);
+    assert_eq!(history.get_streak("Expert", get_date(4)), 0);
+
+    history.start("Beginner", get_date(5));
+    assert_eq!(history.get_streak("Beginner", get_date(5)), 4);
+    history.finish("Beginner", get_date(5), Outcome::Lost, 3000);
+    assert_eq!(history.get_streak("Beginner", get_date(5)), 0);
+    history.attempts.insert(
+        ("Beginner".to_owned(), get_date(2)),
+        Attempt {
+            outcome: Outcome::Lost,
+            time_ms: 0,
+        },
+    );
+    assert_eq!(history.get_streak("Beginner", get_date(4)), 2);
+}
+
+#[test]
+fn test_save() {
+    let path = env::temp_dir().join(format!("minesweep-rs-daily-{}.txt", ::std::process::id()));
+
+    let mut history = DailyHistory::default();
+    history.start("Custom 9x9 10", get_date(1));
+    history.finish("Custom 9x9 10", get_date(1), Outcome::Lost, 4321);
+    history.start("Expert 30x16 99", get_date(2));
+    history.save(&path).unwrap();
+
+    let loaded = DailyHistory::load(&path).unwrap();
+    assert_eq!(
+        loaded.get("Custom 9x9 10", get_date(1)),
+        Some(&Attempt {
+            outcome: Outcome::Lost,
+            time_ms: 4321,
+        })
+    );
+    assert_eq!(
+        loaded.get("Expert 30x16 99", get_date(2)).map(|x| x.outcome),
+        Some(Outcome::Unfinished)
+    );
+    assert_eq!(loaded.attempts.len(), 2);
+
+    fs::remove_file(&path).unwrap();
+    assert!(DailyHistory::load(&path).unwrap().attempts.is_empty());
+}
@@ -0,0 +1,2376 @@
+#[cfg(test)]
+mod tests;
+
+use minesweep_rs::game::preset::PresetRegistry;
+use minesweep_rs::game::{
+    GameState, LoseReason, MineSweeper, Position, Rules, Square, SquareState, VisibleSquare,
+};
+use minesweep_rs::bot::Action;
+use minesweep_rs::config::{Config, ThemeConfig};
+use minesweep_rs::coop::{CoopClient, CoopEvent};
+use minesweep_rs::daily::{self, DailyHistory, Outcome};
+use minesweep_rs::hint::{self, Hint, HintKind};
+use minesweep_rs::palette::{self, Palette, PALETTES};
+use minesweep_rs::puzzle::{self, PuzzleProgress, Rating};
+use minesweep_rs::race::{RaceClient, RaceStatus};
+use minesweep_rs::render::{
+    self, svg, RenderOptions, BACKGROUND_COLOR, CELL_BORDER_COLOR, CELL_COVERED_COLOR,
+    CELL_REVEALED_COLOR, FACE_ICON_SIZE, FLAG_ICON_SIZE, HEART_COLOR, HEART_FONT_SIZE, HEART_SIZE,
+    HUNT_PLAYER_COLORS, HUNT_TURN_BORDER_RADIUS, MARGIN, MINE_ICON_SIZE, NUMBER_FONT_SIZE,
+    PATTERN_COLOR, PATTERN_LINE_RADIUS, PATTERN_SIZE, SQUARE_SIZE, TIME_FONT_SIZE,
+    TIME_RECT_MIN_WIDTH, TOP_BAR_HEIGHT, UI_FONT_SIZE, UI_FONT_Y_OFFSET, UI_RECT_COLOR,
+    UI_RECT_HEIGHT, UI_TEXT_COLOR,
+};
+use minesweep_rs::scores::{self, HighScores, ScoreEntry};
+use minesweep_rs::skin::Skin;
+use minesweep_rs::tutorial::Lesson;
+use bindings::{self, Bindings, Command, Input, COMMANDS};
+use chrono::{Local, NaiveDate};
+use piston_window::character::CharacterCache;
+use piston_window::rectangle::Border;
+use piston_window::*;
+use std::cmp::min;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub struct Icons {
+    pub mine: G2dTexture,
+    pub flag: G2dTexture,
+    pub win_face: G2dTexture,
+    pub ongoing_face: G2dTexture,
+    pub lost_face: G2dTexture,
+    // drawn over the whole cell instead of the theme's colors, only when the skin has them
+    pub cell_covered: Option<G2dTexture>,
+    pub cell_revealed: Option<G2dTexture>,
+}
+
+const BEVEL_LIGHT_COLOR: types::Color = [1.0, 1.0, 1.0, 1.0];
+const BEVEL_SHADOW_COLOR: types::Color = [0.5, 0.5, 0.5, 1.0];
+const BEVEL_WIDTH: f64 = 2.0;
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 4.0;
+const ZOOM_STEP: f64 = 0.25;
+const PAUSE_OVERLAY_COLOR: types::Color = [0.3, 0.3, 0.3, 1.0];
+const RACE_BAR_HEIGHT: u32 = 14;
+const RACE_FONT_SIZE: u32 = 20;
+const RACE_ONGOING_COLOR: types::Color = [0.2, 0.4, 0.9, 1.0];
+const RACE_WON_COLOR: types::Color = [0.13, 0.54, 0.13, 1.0];
+const RACE_TEXT_COLOR: types::Color = [1.0, 1.0, 1.0, 1.0];
+const CURSOR_BORDER_RADIUS: f64 = 1.5;
+const KEYBOARD_CURSOR_COLOR: types::Color = [1.0, 0.85, 0.0, 1.0];
+const SCORES_FONT_SIZE: u32 = 16;
+const SCORES_LINE_HEIGHT: f64 = 10.0;
+const SCORES_CHAR_WIDTH: f64 = 5.0;
+const MAX_NAME_LENGTH: usize = 16;
+const HINT_SAFE_COLOR: types::Color = [0.1, 0.75, 0.2, 1.0];
+const HINT_MINE_COLOR: types::Color = [0.9, 0.1, 0.1, 1.0];
+const HINT_GUESS_COLOR: types::Color = [1.0, 0.55, 0.0, 1.0];
+const HINT_SOURCE_COLOR: types::Color = [0.2, 0.4, 0.9, 1.0];
+const HINT_BORDER_RADIUS: f64 = 2.0;
+const HINT_DURATION: Duration = Duration::from_secs(10);
+const LESSON_MARK_COLOR: types::Color = [0.85, 0.2, 0.85, 1.0];
+// lesson boards are small, the window is kept wide enough for their text
+const LESSON_MIN_WIDTH: u32 = 240;
+const CURSOR_COLORS: [types::Color; 6] = [
+    [0.0, 0.6, 1.0, 1.0],
+    [1.0, 0.6, 0.0, 1.0],
+    [0.7, 0.2, 0.9, 1.0],
+    [0.0, 0.7, 0.3, 1.0],
+    [1.0, 0.2, 0.6, 1.0],
+    [0.5, 0.3, 0.1, 1.0],
+];
+
+// colors the board is drawn with, the skin's layered over the built-in ones and the config's
+// layered over both
+#[derive(Clone)]
+pub struct Theme {
+    pub background: types::Color,
+    pub ui_rect: types::Color,
+    pub ui_text: types::Color,
+    pub cell_covered: types::Color,
+    pub cell_revealed: types::Color,
+    pub cell_border: types::Color,
+    pub mine_revealed: types::Color,
+    pub mine_border: types::Color,
+    pub bevel: bool,
+    pub bevel_light: types::Color,
+    pub bevel_shadow: types::Color,
+    pub numbers: [types::Color; 8],
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        let mut numbers = [[0.0; 4]; 8];
+        for (i, color) in numbers.iter_mut().enumerate() {
+            *color = Gui::get_text_color(i as u32 + 1);
+        }
+
+        Theme {
+            background: BACKGROUND_COLOR,
+            ui_rect: UI_RECT_COLOR,
+            ui_text: UI_TEXT_COLOR,
+            cell_covered: CELL_COVERED_COLOR,
+            cell_revealed: CELL_REVEALED_COLOR,
+            cell_border: CELL_BORDER_COLOR,
+            mine_revealed: palette::DEFAULT.mine_revealed,
+            mine_border: palette::DEFAULT.mine_border,
+            bevel: false,
+            bevel_light: BEVEL_LIGHT_COLOR,
+            bevel_shadow: BEVEL_SHADOW_COLOR,
+            numbers,
+        }
+    }
+}
+
+impl Theme {
+    pub fn from_skin(skin: &Skin, config: &ThemeConfig) -> Theme {
+        let mut theme = Theme::default().with_config(&skin.manifest.theme);
+        theme.bevel = skin.manifest.bevel;
+        theme.with_config(config)
+    }
+
+    // anything the config leaves out keeps its color
+    pub fn with_config(&self, config: &ThemeConfig) -> Theme {
+        let mut numbers = self.numbers;
+        for (color, new_color) in numbers.iter_mut().zip(config.numbers.iter().flatten()) {
+            *color = *new_color;
+        }
+
+        Theme {
+            background: config.background.unwrap_or(self.background),
+            ui_rect: config.ui_rect.unwrap_or(self.ui_rect),
+            ui_text: config.ui_text.unwrap_or(self.ui_text),
+            cell_covered: config.cell_covered.unwrap_or(self.cell_covered),
+            cell_revealed: config.cell_revealed.unwrap_or(self.cell_revealed),
+            cell_border: config.cell_border.unwrap_or(self.cell_border),
+            mine_revealed: config.mine_revealed.unwrap_or(self.mine_revealed),
+            mine_border: config.mine_border.unwrap_or(self.mine_border),
+            bevel: self.bevel,
+            bevel_light: config.bevel_light.unwrap_or(self.bevel_light),
+            bevel_shadow: config.bevel_shadow.unwrap_or(self.bevel_shadow),
+            numbers,
+        }
+    }
+
+    pub fn with_palette(&self, palette: &Palette) -> Theme {
+        Theme {
+            mine_revealed: palette.mine_revealed,
+            mine_border: palette.mine_border,
+            numbers: palette.numbers,
+            ..self.clone()
+        }
+    }
+
+    fn get_number_color(&self, num: u32) -> types::Color {
+        match num {
+            1..=8 => self.numbers[num as usize - 1],
+            _ => Gui::get_text_color(num),
+        }
+    }
+}
+
+fn load_texture(window: &mut PistonWindow, path: &Path) -> Option<G2dTexture> {
+    match Texture::from_path(&mut window.factory, path, Flip::None, &TextureSettings::new()) {
+        Ok(texture) => Some(texture),
+        Err(err) => {
+            eprintln!("could not load {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+// the skin's texture, or the built-in one when the skin has none or it does not load
+fn load_skin_texture(
+    window: &mut PistonWindow,
+    assets: &Path,
+    skin: &Skin,
+    name: &str,
+    builtin: &str,
+) -> G2dTexture {
+    skin.get_texture(name)
+        .and_then(|path| load_texture(window, &path))
+        .or_else(|| load_texture(window, &assets.join(builtin)))
+        .unwrap()
+}
+
+impl Icons {
+    pub fn load(window: &mut PistonWindow, assets: &Path, skin: &Skin) -> Icons {
+        Icons {
+            mine: load_skin_texture(window, assets, skin, "mine", "mine.png"),
+            flag: load_skin_texture(window, assets, skin, "flag", "flag.png"),
+            win_face: load_skin_texture(window, assets, skin, "win_face", "cool.png"),
+            ongoing_face: load_skin_texture(window, assets, skin, "ongoing_face", "happy.png"),
+            lost_face: load_skin_texture(window, assets, skin, "lost_face", "shocked.png"),
+            cell_covered: skin
+                .get_texture("cell_covered")
+                .and_then(|path| load_texture(window, &path)),
+            cell_revealed: skin
+                .get_texture("cell_revealed")
+                .and_then(|path| load_texture(window, &path)),
+        }
+    }
+}
+
+pub fn load_glyphs(window: &mut PistonWindow, assets: &Path, skin: &Skin) -> Glyphs {
+    let settings = TextureSettings::new().filter(Filter::Nearest);
+
+    if let Some(path) = skin.get_font() {
+        match Glyphs::new(&path, window.factory.clone(), settings) {
+            Ok(glyphs) => return glyphs,
+            Err(err) => eprintln!("could not load {}: {}", path.display(), err),
+        }
+    }
+
+    Glyphs::new(
+        assets.join("Andale-Mono.ttf"),
+        window.factory.clone(),
+        settings,
+    ).unwrap()
+}
+
+// breaks the text at spaces into lines of at most width characters, a longer word gets a line
+// of its own
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// the scale that fits the unscaled base size into the window keeping its shape, and the origin
+// that centers it
+fn get_fit(width: u32, height: u32, base_width: u32, base_height: u32) -> (f64, [f64; 2]) {
+    let (base_width, base_height) = (f64::from(base_width), f64::from(base_height));
+    let scale = (f64::from(width) / base_width)
+        .min(f64::from(height) / base_height)
+        .max(0.01);
+    let origin = [
+        (f64::from(width) - base_width * scale) * 0.5,
+        (f64::from(height) - base_height * scale) * 0.5,
+    ];
+
+    (scale, origin)
+}
+
+// a point in the window as it would be before scaling
+fn unscale(x: f64, y: f64, scale: f64, origin: [f64; 2]) -> (f64, f64) {
+    ((x - origin[0]) / scale, (y - origin[1]) / scale)
+}
+
+// the square under a point measured unscaled from the board's top left corner
+fn get_square_at(x: f64, y: f64, cols: u32, rows: u32) -> Option<Position> {
+    if x >= 0.0
+        && y >= 0.0
+        && x < f64::from(cols * SQUARE_SIZE)
+        && y < f64::from(rows * SQUARE_SIZE)
+    {
+        Some(Position(y as u32 / SQUARE_SIZE, x as u32 / SQUARE_SIZE))
+    } else {
+        None
+    }
+}
+
+// the cursor moved by the given rows and columns, None when that would leave the board
+fn get_moved_cursor(
+    cursor: Position,
+    d_row: i64,
+    d_col: i64,
+    cols: u32,
+    rows: u32,
+) -> Option<Position> {
+    let row = i64::from(cursor.0) + d_row;
+    let col = i64::from(cursor.1) + d_col;
+
+    if row >= 0 && col >= 0 && row < i64::from(rows) && col < i64::from(cols) {
+        Some(Position(row as u32, col as u32))
+    } else {
+        None
+    }
+}
+
+// the nearest square on the board, for a cursor left outside after it shrinks
+fn get_clamped_cursor(cursor: Position, cols: u32, rows: u32) -> Position {
+    Position(min(cursor.0, rows - 1), min(cursor.1, cols - 1))
+}
+
+// the command picked on the bindings screen, and whether the next key or mouse button pressed
+// is bound to it
+struct BindingEditor {
+    selected: usize,
+    capturing: bool,
+}
+
+// the day's boards, played by the classic rules, the first started on every preset being the
+// official attempt
+struct Daily {
+    date: NaiveDate,
+    history: DailyHistory,
+    path: Option<PathBuf>,
+    // whether the board being played is the official attempt
+    official: bool,
+}
+
+// the lessons being played through, the step of the one on the board, and whether the last move
+// was not the one the step asks for
+struct Tutorial {
+    lessons: Vec<Lesson>,
+    lesson: usize,
+    step: usize,
+    missed: bool,
+}
+
+// the hint on show and the board it was worked out for, it goes once the board changes
+struct ShownHint {
+    hint: Hint,
+    board: Vec<Vec<VisibleSquare>>,
+    shown_at: Instant,
+}
+
+// where the puzzles come from, a file's puzzles by their keys with their ratings or the
+// generator, which makes them on demand for the preset being played
+pub enum PuzzleSource {
+    File(Vec<(String, Rating)>),
+    Generated(Rating),
+}
+
+// the puzzle being played, numbered from 0, and the ones solved so far
+struct Puzzles {
+    source: PuzzleSource,
+    number: usize,
+    // the puzzle's starting board, which names it in the progress
+    key: String,
+    rating: Rating,
+    progress: PuzzleProgress,
+    path: Option<PathBuf>,
+}
+
+pub struct Gui {
+    game: MineSweeper,
+    selected_position: Option<Position>,
+    // the square keyboard moves act on, only drawn once the keyboard has moved it
+    cursor: Position,
+    show_cursor: bool,
+    face_selected: bool,
+    // mouse buttons held down, in the order they were pressed
+    mouse_down: Vec<MouseButton>,
+    face_button_rect: [f64; 4],
+    // the size the window is asked for, as a multiple of the board's size in pixels
+    zoom: f64,
+    // how much everything is scaled to fit the window as it is, and where the board starts in it
+    scale: f64,
+    origin: [f64; 2],
+    // device pixels per window pixel, above 1 on HiDPI screens
+    dpi: f64,
+    presets: PresetRegistry,
+    // index of the preset being played
+    preset: usize,
+    rules: Rules,
+    race: Option<RaceClient>,
+    coop: Option<CoopClient>,
+    // where the other players of a shared board are pointing, by player id
+    cursors: HashMap<u32, Position>,
+    high_scores: HighScores,
+    scores_path: Option<PathBuf>,
+    // the name typed so far after a win good enough for the leaderboard
+    name_prompt: Option<String>,
+    show_scores: bool,
+    // the built-in skin first, then the ones found in the assets
+    skins: Vec<Skin>,
+    skin: usize,
+    theme_config: ThemeConfig,
+    // index into the palettes, the default leaves the skin's and config's colors alone
+    palette: usize,
+    high_contrast: bool,
+    // shapes on flagged and exploded cells
+    patterns: bool,
+    theme: Theme,
+    bindings: Bindings,
+    left_handed: bool,
+    // the bindings screen while it is open
+    editor: Option<BindingEditor>,
+    // the config file changes to the bindings and colors are written back to
+    config_path: Option<PathBuf>,
+    daily: Option<Daily>,
+    puzzles: Option<Puzzles>,
+    hint: Option<ShownHint>,
+    tutorial: Option<Tutorial>,
+}
+
+impl Gui {
+    pub fn new(presets: PresetRegistry, preset: usize, rules: Rules) -> Gui {
+        let scores_path = scores::get_default_path();
+        let high_scores = scores_path
+            .as_ref()
+            .and_then(|path| HighScores::load(path).ok())
+            .unwrap_or_default();
+
+        Gui {
+            game: MineSweeper::new_from_preset(presets.get(preset).unwrap(), rules),
+            selected_position: None,
+            cursor: Position(0, 0),
+            show_cursor: false,
+            face_selected: false,
+            mouse_down: Vec::new(),
+            face_button_rect: [0.0, 0.0, 0.0, 0.0],
+            zoom: 1.0,
+            scale: 1.0,
+            origin: [0.0, 0.0],
+            dpi: 1.0,
+            presets,
+            preset,
+            rules,
+            race: None,
+            coop: None,
+            cursors: HashMap::new(),
+            high_scores,
+            scores_path,
+            name_prompt: None,
+            show_scores: false,
+            skins: vec![Skin::builtin()],
+            skin: 0,
+            theme_config: ThemeConfig::default(),
+            palette: 0,
+            high_contrast: false,
+            patterns: false,
+            theme: Theme::default(),
+            bindings: Bindings::default(),
+            left_handed: false,
+            editor: None,
+            config_path: None,
+            daily: None,
+            puzzles: None,
+            hint: None,
+            tutorial: None,
+        }
+    }
+
+    // the bindings edited in the window are saved back to the config at path
+    pub fn apply_config(&mut self, config: &Config, path: Option<PathBuf>) {
+        self.theme_config = config.theme.clone();
+        self.palette = match config.palette {
+            Some(ref name) => palette::find_palette(name).unwrap_or_else(|| {
+                eprintln!("unknown palette {}, using the default", name);
+                0
+            }),
+            None => 0,
+        };
+        self.high_contrast = config.high_contrast;
+        self.patterns = config.patterns;
+        self.update_theme();
+
+        self.bindings = Bindings::from_config(&config.keys, config.left_handed);
+        self.left_handed = config.left_handed;
+        self.config_path = path;
+    }
+
+    // the skins to pick from after the built-in one, starting on the one with the name if any
+    pub fn set_skins(&mut self, skins: Vec<Skin>, name: Option<&str>) {
+        self.skins.truncate(1);
+        self.skins.extend(skins);
+
+        let index = match name {
+            Some(name) => match self
+                .skins
+                .iter()
+                .position(|x| x.get_name().eq_ignore_ascii_case(name))
+            {
+                Some(index) => index,
+                None => {
+                    eprintln!("unknown skin {}, using the built-in one", name);
+                    0
+                }
+            },
+            None => 0,
+        };
+        self.select_skin(index);
+    }
+
+    fn select_skin(&mut self, index: usize) {
+        self.skin = index;
+        self.update_theme();
+    }
+
+    // the skin, then the config's theme, then the palette, then high contrast over everything
+    fn update_theme(&mut self) {
+        let mut theme = Theme::from_skin(self.get_skin(), &self.theme_config);
+        if self.palette > 0 {
+            theme = theme.with_palette(&PALETTES[self.palette]);
+        }
+        if self.high_contrast {
+            theme = theme.with_config(&palette::get_high_contrast_theme());
+        }
+        self.theme = theme;
+    }
+
+    // the textures and font are loaded by the caller whenever this changes
+    pub fn get_skin(&self) -> &Skin {
+        &self.skins[self.skin]
+    }
+
+    // today's board for the preset, recorded in the daily history at path
+    pub fn start_daily(&mut self, path: Option<PathBuf>) {
+        let history = path
+            .as_ref()
+            .and_then(|path| DailyHistory::load(path).ok())
+            .unwrap_or_default();
+        let date = Local::now().date_naive();
+
+        self.rules = Rules::default();
+        self.game = daily::new_game(date, self.presets.get(self.preset).unwrap());
+        self.daily = Some(Daily {
+            date,
+            history,
+            path,
+            official: false,
+        });
+    }
+
+    // the first puzzle not solved yet, progress is kept in the file at path, the game carries on
+    // as it was when there is no puzzle to play
+    pub fn start_puzzles(&mut self, source: PuzzleSource, path: Option<PathBuf>) {
+        let progress = path
+            .as_ref()
+            .and_then(|path| PuzzleProgress::load(path).ok())
+            .unwrap_or_default();
+
+        self.puzzles = Some(Puzzles {
+            source,
+            number: 0,
+            key: String::new(),
+            rating: Rating::Easy,
+            progress,
+            path,
+        });
+
+        let number = self.get_first_unsolved_puzzle();
+        if self.load_puzzle(number) {
+            self.rules = Rules::default();
+            self.daily = None;
+        } else {
+            eprintln!("no puzzle of that rating fits the board, playing the normal game");
+            self.puzzles = None;
+        }
+    }
+
+    fn get_first_unsolved_puzzle(&self) -> usize {
+        let puzzles = match self.puzzles {
+            Some(ref puzzles) => puzzles,
+            None => return 0,
+        };
+
+        match puzzles.source {
+            PuzzleSource::File(ref layouts) => layouts
+                .iter()
+                .position(|x| !puzzles.progress.is_solved(&x.0))
+                .unwrap_or(0),
+            PuzzleSource::Generated(rating) => {
+                let preset = self.presets.get(self.preset).unwrap();
+                (0..)
+                    .find(|&number| {
+                        let key = puzzle::get_generated_key(preset, rating, number as u64);
+                        !puzzles.progress.is_solved(&key)
+                    })
+                    .unwrap()
+            }
+        }
+    }
+
+    // false when the generator finds no puzzle of the rating for the board, or the file's puzzle
+    // does not load
+    fn load_puzzle(&mut self, number: usize) -> bool {
+        let preset = self.presets.get(self.preset).unwrap();
+        let puzzles = match self.puzzles {
+            Some(ref mut puzzles) => puzzles,
+            None => return false,
+        };
+
+        let (game, key, rating) = match puzzles.source {
+            PuzzleSource::File(ref layouts) => {
+                let (ref key, rating) = layouts[number % layouts.len()];
+                let layout = key.replace('/', "\n");
+                match MineSweeper::from_layout(&layout, Rules::default()) {
+                    Ok(game) => (game, key.clone(), rating),
+                    Err(err) => {
+                        eprintln!("could not load puzzle {}: {}", number + 1, err);
+                        return false;
+                    }
+                }
+            }
+            PuzzleSource::Generated(rating) => {
+                match puzzle::get_generated_puzzle(preset, rating, number as u64) {
+                    Some(game) => {
+                        let key = puzzle::get_generated_key(preset, rating, number as u64);
+                        (game, key, rating)
+                    }
+                    None => return false,
+                }
+            }
+        };
+
+        puzzles.number = number;
+        puzzles.key = key;
+        puzzles.rating = rating;
+        self.game = game;
+        self.clamp_cursor();
+        true
+    }
+
+    fn step_puzzle(&mut self, step: i64, window: &mut PistonWindow) {
+        let (number, count) = match self.puzzles {
+            Some(ref puzzles) => match puzzles.source {
+                PuzzleSource::File(ref layouts) => (puzzles.number, layouts.len()),
+                PuzzleSource::Generated(_) => (puzzles.number, usize::MAX),
+            },
+            None => return,
+        };
+
+        // a file's puzzles go round, generated ones start at the first
+        let number = match count {
+            usize::MAX => (number as i64 + step).max(0) as usize,
+            _ => (number as i64 + step).rem_euclid(count as i64) as usize,
+        };
+        if self.load_puzzle(number) {
+            self.resize_window(window);
+        }
+    }
+
+    // solved puzzles are saved as soon as they are
+    fn update_puzzles(&mut self) {
+        let puzzles = match self.puzzles {
+            Some(ref mut puzzles) => puzzles,
+            None => return,
+        };
+
+        if self.game.state != GameState::Won || !puzzles.progress.add(&puzzles.key) {
+            return;
+        }
+
+        if let Some(ref path) = puzzles.path {
+            if let Err(err) = puzzles.progress.save(path) {
+                eprintln!("could not save puzzle progress to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    // the lessons in order from the first, which must not be empty
+    pub fn start_tutorial(&mut self, lessons: Vec<Lesson>) {
+        assert!(!lessons.is_empty());
+
+        self.rules = Rules::default();
+        self.daily = None;
+        self.puzzles = None;
+        self.tutorial = Some(Tutorial {
+            lessons,
+            lesson: 0,
+            step: 0,
+            missed: false,
+        });
+        self.load_lesson(0);
+    }
+
+    fn load_lesson(&mut self, index: usize) {
+        if let Some(ref mut tutorial) = self.tutorial {
+            self.game = tutorial.lessons[index].new_game();
+            tutorial.lesson = index;
+            tutorial.step = 0;
+            tutorial.missed = false;
+        }
+        self.hint = None;
+        self.clamp_cursor();
+    }
+
+    fn step_lesson(&mut self, step: i64, window: &mut PistonWindow) {
+        let (lesson, count) = match self.tutorial {
+            Some(ref tutorial) => (tutorial.lesson as i64, tutorial.lessons.len() as i64),
+            None => return,
+        };
+
+        let index = (lesson + step).clamp(0, count - 1);
+        if index != lesson {
+            self.load_lesson(index as usize);
+            self.resize_window(window);
+        }
+    }
+
+    // only the move the step waits for is played, anything else is ignored
+    fn apply_lesson_action(&mut self, action: &Action) {
+        let tutorial = match self.tutorial {
+            Some(ref mut tutorial) => tutorial,
+            None => return,
+        };
+        let step = match tutorial.lessons[tutorial.lesson].steps.get(tutorial.step) {
+            Some(step) => step,
+            None => return,
+        };
+
+        if !step.accepts(action) {
+            tutorial.missed = true;
+            return;
+        }
+
+        if step.action.is_some() {
+            action.apply(&mut self.game);
+        }
+        tutorial.step += 1;
+        tutorial.missed = false;
+    }
+
+    // the step's text, then what the lesson waits for
+    fn get_lesson_lines(&self) -> Vec<String> {
+        let tutorial = match self.tutorial {
+            Some(ref tutorial) => tutorial,
+            None => return Vec::new(),
+        };
+        let width = (f64::from(self.get_base_size()[0]) - MARGIN * 4.0) / SCORES_CHAR_WIDTH;
+        let steps = &tutorial.lessons[tutorial.lesson].steps;
+
+        let (text, status) = match steps.get(tutorial.step) {
+            Some(step) if self.game.state == GameState::Ongoing => {
+                let status = if tutorial.missed {
+                    "not quite, try again"
+                } else if step.action.is_none() {
+                    "space or click the board to go on"
+                } else {
+                    ""
+                };
+                (&step.text[..], status)
+            }
+            _ if tutorial.lesson + 1 < tutorial.lessons.len() => {
+                ("Lesson done.", "page up: next lesson, r: again")
+            }
+            _ => ("That was the last lesson.", "r: again, esc: quit"),
+        };
+
+        let mut lines = wrap_text(text, width as usize);
+        lines.push(String::new());
+        lines.push(status.to_owned());
+        lines
+    }
+
+    // tall enough for the longest step of the lesson, so the window keeps its size through it
+    fn get_lesson_panel_height(&self) -> u32 {
+        let tutorial = match self.tutorial {
+            Some(ref tutorial) => tutorial,
+            None => return 0,
+        };
+        let width = f64::from(self.get_board_width().max(LESSON_MIN_WIDTH));
+        let width = ((width - MARGIN * 4.0) / SCORES_CHAR_WIDTH) as usize;
+
+        let lines = tutorial.lessons[tutorial.lesson]
+            .steps
+            .iter()
+            .map(|x| wrap_text(&x.text, width).len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        (SCORES_LINE_HEIGHT * lines as f64 + MARGIN * 2.0) as u32
+    }
+
+    // the board is centered over the lesson text when narrower
+    fn get_board_offset(&self) -> f64 {
+        f64::from(self.get_base_size()[0] - self.get_board_width()) * 0.5
+    }
+
+    // the day and the streak on the preset while playing the daily boards, the puzzle and its
+    // rating while playing puzzles, the lesson while playing the tutorial
+    pub fn get_title(&self) -> String {
+        if let Some(ref tutorial) = self.tutorial {
+            return format!(
+                "Mine Sweeper - Tutorial {} of {}: {}",
+                tutorial.lesson + 1,
+                tutorial.lessons.len(),
+                tutorial.lessons[tutorial.lesson].title
+            );
+        }
+
+        if let Some(ref puzzles) = self.puzzles {
+            let count = match puzzles.source {
+                PuzzleSource::File(ref layouts) => format!(" of {}", layouts.len()),
+                PuzzleSource::Generated(_) => String::new(),
+            };
+            let solved = if puzzles.progress.is_solved(&puzzles.key) {
+                ", solved"
+            } else {
+                ""
+            };
+
+            return format!(
+                "Mine Sweeper - Puzzle {}{} ({}{})",
+                puzzles.number + 1,
+                count,
+                puzzles.rating.get_name(),
+                solved
+            );
+        }
+
+        let daily = match self.daily {
+            Some(ref daily) => daily,
+            None => return "Mine Sweeper".to_owned(),
+        };
+
+        let preset = self.presets.get(self.preset).unwrap();
+        let key = daily::get_board_key(preset);
+        let practice = if daily.official || daily.history.get(&key, daily.date).is_none() {
+            ""
+        } else {
+            " (practice)"
+        };
+
+        format!(
+            "Mine Sweeper - Daily {} {}{} - streak {}",
+            daily.date.format(daily::DATE_FORMAT),
+            preset.name,
+            practice,
+            daily.history.get_streak(&key, daily.date)
+        )
+    }
+
+    // switches to the host's board, every move goes through the host from now on
+    pub fn join_coop(&mut self, client: CoopClient) {
+        self.game = client.board.new_game();
+        self.rules = self.game.rules;
+        self.daily = None;
+        self.puzzles = None;
+        self.tutorial = None;
+        self.coop = Some(client);
+    }
+
+    // switches to the race board, which every player shares through its seed
+    pub fn join_race(&mut self, client: RaceClient) {
+        self.game = client.board.new_game();
+        self.rules = self.game.rules;
+        self.daily = None;
+        self.puzzles = None;
+        self.tutorial = None;
+        self.race = Some(client);
+    }
+
+    // races get a second band under the top bar for the opponents' progress
+    fn get_top_bar_height(&self) -> u32 {
+        if self.race.is_some() {
+            TOP_BAR_HEIGHT + RACE_BAR_HEIGHT
+        } else {
+            TOP_BAR_HEIGHT
+        }
+    }
+
+    fn select_preset(&mut self, index: usize, window: &mut PistonWindow) {
+        // generated puzzles carry on with the first one not solved on the new board
+        if self.puzzles.is_some() {
+            let previous = self.preset;
+            self.preset = index;
+            let number = self.get_first_unsolved_puzzle();
+            if self.presets.get(index).is_some() && self.load_puzzle(number) {
+                self.resize_window(window);
+            } else {
+                self.preset = previous;
+            }
+            return;
+        }
+
+        if let Some(preset) = self.presets.get(index) {
+            self.game = match self.daily {
+                Some(ref mut daily) => {
+                    daily.official = false;
+                    daily::new_game(daily.date, preset)
+                }
+                None => MineSweeper::new_from_preset(preset, self.rules),
+            };
+            self.preset = index;
+            self.clamp_cursor();
+            self.resize_window(window);
+        }
+    }
+
+    // the board and bars before any scaling, convention [width, height]
+    fn get_board_width(&self) -> u32 {
+        self.game.cols * SQUARE_SIZE
+    }
+
+    // lessons get their text under the board
+    fn get_base_size(&self) -> [u32; 2] {
+        let width = match self.tutorial {
+            Some(_) => self.get_board_width().max(LESSON_MIN_WIDTH),
+            None => self.get_board_width(),
+        };
+
+        [
+            width,
+            self.game.rows * SQUARE_SIZE
+                + self.get_top_bar_height()
+                + self.get_lesson_panel_height(),
+        ]
+    }
+
+    // convention [width, height]
+    pub fn get_window_size(&self) -> [u32; 2] {
+        let [width, height] = self.get_base_size();
+        [
+            (f64::from(width) * self.zoom).round() as u32,
+            (f64::from(height) * self.zoom).round() as u32,
+        ]
+    }
+
+    pub fn set_zoom(&mut self, zoom: f64) {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        self.scale = self.zoom;
+        self.origin = [0.0, 0.0];
+    }
+
+    fn resize_window(&mut self, window: &mut PistonWindow) {
+        let zoom = self.zoom;
+        self.set_zoom(zoom);
+        window.set_size(self.get_window_size());
+    }
+
+    // the board keeps its shape, scaled as large as fits and centered in the window,
+    // and the zoom follows so switching boards keeps the size the player picked
+    pub fn handle_resize(&mut self, width: u32, height: u32) {
+        let [base_width, base_height] = self.get_base_size();
+        let (scale, origin) = get_fit(width, height, base_width, base_height);
+
+        self.scale = scale;
+        self.zoom = self.scale.clamp(MIN_ZOOM, MAX_ZOOM);
+        self.origin = origin;
+    }
+
+    // glyphs are rasterized at twice their size on screen and drawn at half, more when
+    // scaled up or on a HiDPI screen so text stays sharp
+    fn get_font_factor(&self) -> f64 {
+        (self.scale * self.dpi * 0.5).ceil().max(1.0)
+    }
+
+    fn draw_text(
+        &self,
+        color: types::Color,
+        font_size: u32,
+        label: &str,
+        glyphs: &mut Glyphs,
+        transform: math::Matrix2d,
+        g: &mut G2d,
+    ) {
+        let factor = self.get_font_factor();
+        text(
+            color,
+            (f64::from(font_size) * factor) as u32,
+            label,
+            glyphs,
+            transform.zoom(1.0 / factor),
+            g,
+        ).unwrap();
+    }
+
+    pub fn update(&mut self) {
+        // the countdown can run out without any input from the player
+        if self.game.state == GameState::Ongoing && self.game.rules.time_limit.is_some() {
+            self.game.update_game_state();
+        }
+
+        self.update_daily();
+        self.update_puzzles();
+        self.update_hint();
+
+        if let Some(ref mut client) = self.race {
+            // a lost connection only stops the updates, the board stays playable
+            let _ = client.send_progress(&self.game);
+        }
+
+        let events = self
+            .coop
+            .as_ref()
+            .map(|client| client.poll_events())
+            .unwrap_or_default();
+
+        for event in events {
+            match event {
+                CoopEvent::Board(board) => {
+                    self.game = board.new_game();
+                    self.clamp_cursor();
+                }
+                CoopEvent::Move(_, action) => action.apply(&mut self.game),
+                CoopEvent::Cursor(id, pos) => {
+                    self.cursors.insert(id, pos);
+                }
+                CoopEvent::Left(id) => {
+                    self.cursors.remove(&id);
+                }
+            }
+        }
+    }
+
+    pub fn handle_focus(&mut self, focused: bool) {
+        // hide the board when the window loses focus, resuming is left to the player,
+        // a shared board keeps going for everyone else
+        if !focused && self.coop.is_none() {
+            self.game.pause();
+        }
+    }
+
+    pub fn handle_mouse_position(&mut self, x: f64, y: f64) {
+        // everything below is laid out unscaled
+        let (x, y) = unscale(x, y, self.scale, self.origin);
+        let x = x - self.get_board_offset();
+
+        // face button processing
+        self.face_selected = x >= self.face_button_rect[0]
+            && y >= self.face_button_rect[1]
+            && (x <= self.face_button_rect[0] + self.face_button_rect[2])
+            && (y <= self.face_button_rect[1] + self.face_button_rect[3]);
+
+        let y_board = y - f64::from(self.get_top_bar_height());
+
+        if let Some(pos) = get_square_at(x, y_board, self.game.cols, self.game.rows) {
+            if self.selected_position != Some(pos) {
+                if let Some(ref mut client) = self.coop {
+                    let _ = client.send_cursor(&pos);
+                }
+
+                // the keyboard picks up wherever the mouse left off
+                self.cursor = pos;
+                self.show_cursor = false;
+            }
+
+            self.selected_position = Some(pos);
+        } else {
+            self.selected_position = None;
+        }
+    }
+
+    pub fn handle_mouse_click(&mut self, button: MouseButton, window: &mut PistonWindow) {
+        self.mouse_down.retain(|x| *x != button);
+
+        // the board waits until the name is in or the bindings screen is closed
+        if self.editor.is_some() {
+            if self.bindings.get(Input::Mouse(button)) == Some(Command::Bindings) {
+                self.editor = None;
+            }
+            return;
+        }
+        if self.name_prompt.is_some() {
+            return;
+        }
+
+        let was_ongoing = self.game.state == GameState::Ongoing;
+
+        // the face resets whatever the button
+        if self.face_selected {
+            self.reset_game(window);
+        } else {
+            match self.bindings.get(Input::Mouse(button)) {
+                Some(command @ Command::Reveal)
+                | Some(command @ Command::Flag)
+                | Some(command @ Command::Chord) => self.handle_mouse_click_position(command),
+                Some(command) => self.run_command(command, window),
+                None => (),
+            }
+        }
+
+        self.game.update_game_state();
+
+        if was_ongoing && self.game.state == GameState::Won {
+            self.check_high_score();
+        }
+    }
+
+    fn get_scores_key(&self) -> String {
+        scores::get_board_key(self.presets.get(self.preset).unwrap(), &self.game)
+    }
+
+    // only classic solo games played without undo make the leaderboard
+    fn check_high_score(&mut self) {
+        // a daily board can be replayed knowing where the mines are, and puzzles have no timer
+        if self.coop.is_some()
+            || self.daily.is_some()
+            || self.puzzles.is_some()
+            || self.tutorial.is_some()
+            || self.game.rules != Rules::default()
+            || self.game.get_undo_count() > 0
+        {
+            return;
+        }
+
+        let time_ms = self.game.final_time_ms().unwrap_or(0);
+        if self.high_scores.qualifies(&self.get_scores_key(), time_ms) {
+            self.name_prompt = Some(String::new());
+        }
+    }
+
+    fn submit_high_score(&mut self) {
+        let name = match self.name_prompt.take() {
+            Some(ref name) if name.trim().is_empty() => "anonymous".to_owned(),
+            Some(name) => name,
+            None => return,
+        };
+
+        let key = self.get_scores_key();
+        self.high_scores.add(&key, ScoreEntry::new(&name, &self.game));
+        self.show_scores = true;
+
+        if let Some(ref path) = self.scores_path {
+            if let Err(err) = self.high_scores.save(path) {
+                eprintln!("could not save high scores to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    pub fn handle_text(&mut self, text: &str) {
+        if let Some(ref mut name) = self.name_prompt {
+            for x in text.chars().filter(|x| !x.is_control()) {
+                if name.chars().count() < MAX_NAME_LENGTH {
+                    name.push(x);
+                }
+            }
+        }
+    }
+
+    // a reveal and a flag button held together chord, whichever is let go first
+    fn handle_mouse_click_position(&mut self, command: Command) {
+        let curr_pos = match self.selected_position {
+            Some(ref pos) if self.game.state == GameState::Ongoing && !self.game.is_paused() => {
+                *pos
+            }
+            _ => return,
+        };
+
+        let action = match command {
+            Command::Reveal if self.is_mouse_held(Command::Flag) => Action::Chord(curr_pos),
+            Command::Flag if self.is_mouse_held(Command::Reveal) => Action::Chord(curr_pos),
+            Command::Reveal => Action::Reveal(curr_pos),
+            Command::Flag => Action::Flag(curr_pos),
+            Command::Chord => Action::Chord(curr_pos),
+            _ => return,
+        };
+
+        self.apply_action(&action);
+    }
+
+    fn is_mouse_held(&self, command: Command) -> bool {
+        self.mouse_down
+            .iter()
+            .any(|x| self.bindings.get(Input::Mouse(*x)) == Some(command))
+    }
+
+    fn apply_action(&mut self, action: &Action) {
+        if self.tutorial.is_some() {
+            self.apply_lesson_action(action);
+            return;
+        }
+
+        match self.coop {
+            // the move shows up once the host sends it back
+            Some(ref mut client) => {
+                let _ = client.send_action(action);
+            }
+            None => {
+                self.game.save_undo();
+                action.apply(&mut self.game);
+                self.game.drop_undo_if_unchanged();
+            }
+        }
+    }
+
+    // moves from the keyboard go to the square under the cursor
+    fn handle_cursor_action(&mut self, action: &Action) {
+        self.show_cursor = true;
+
+        if self.game.state != GameState::Ongoing || self.game.is_paused() {
+            return;
+        }
+
+        self.apply_action(action);
+        self.game.update_game_state();
+
+        if self.game.state == GameState::Won {
+            self.check_high_score();
+        }
+    }
+
+    fn move_cursor(&mut self, d_row: i64, d_col: i64) {
+        let moved = get_moved_cursor(self.cursor, d_row, d_col, self.game.cols, self.game.rows);
+
+        if let (true, Some(pos)) = (self.show_cursor, moved) {
+            self.cursor = pos;
+
+            if let Some(ref mut client) = self.coop {
+                let _ = client.send_cursor(&self.cursor);
+            }
+        }
+
+        // the first press only brings the cursor up where it is
+        self.show_cursor = true;
+    }
+
+    // keeps the cursor on the board after it shrinks
+    fn clamp_cursor(&mut self) {
+        self.cursor = get_clamped_cursor(self.cursor, self.game.cols, self.game.rows);
+    }
+
+    fn reset_game(&mut self, window: &mut PistonWindow) {
+        self.hint = None;
+
+        // a lesson starts over from its first step
+        if let Some(index) = self.tutorial.as_ref().map(|x| x.lesson) {
+            self.load_lesson(index);
+            return;
+        }
+
+        // a solved puzzle moves on to the next one, any other starts over
+        if let Some(number) = self.puzzles.as_ref().map(|x| x.number) {
+            match self.game.state {
+                GameState::Won => self.step_puzzle(1, window),
+                _ => {
+                    self.load_puzzle(number);
+                }
+            }
+            return;
+        }
+
+        match (&mut self.race, &mut self.coop) {
+            // a race has to be finished on the board it started with
+            (Some(_), _) => (),
+            // the host only deals a new board once the current one is over
+            (_, Some(client)) => {
+                let _ = client.send_reset();
+            }
+            _ => match self.daily {
+                // the same board again, which only the first time is official
+                Some(ref mut daily) => {
+                    daily.official = false;
+                    let preset = self.presets.get(self.preset).unwrap();
+                    self.game.reset_with_seed(daily::get_seed(daily.date, preset));
+                }
+                None => self.game.reset(),
+            },
+        }
+    }
+
+    // the official attempt is recorded once started, so leaving it counts, and again once over
+    fn update_daily(&mut self) {
+        let daily = match self.daily {
+            Some(ref mut daily) => daily,
+            None => return,
+        };
+        let key = daily::get_board_key(self.presets.get(self.preset).unwrap());
+
+        let changed = if !daily.official {
+            daily.official = self.game.is_started() && daily.history.start(&key, daily.date);
+            daily.official
+        } else if self.game.state != GameState::Ongoing {
+            let outcome = match self.game.state {
+                GameState::Won => Outcome::Won,
+                _ => Outcome::Lost,
+            };
+            let time_ms = self.game.final_time_ms().unwrap_or(0);
+            daily.history.finish(&key, daily.date, outcome, time_ms);
+            daily.official = false;
+            true
+        } else {
+            false
+        };
+
+        if let (true, Some(path)) = (changed, daily.path.as_ref()) {
+            if let Err(err) = daily.history.save(path) {
+                eprintln!("could not save daily history to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    // counted against the game once shown, asking again while it is up shows the same one
+    fn show_hint(&mut self) {
+        if self.game.state != GameState::Ongoing || self.game.is_paused() || self.hint.is_some() {
+            return;
+        }
+
+        if let Some(hint) = hint::find_hint(&self.game) {
+            self.game.add_hint();
+            self.hint = Some(ShownHint {
+                hint,
+                board: self.game.get_visible_board(),
+                shown_at: Instant::now(),
+            });
+        }
+    }
+
+    fn update_hint(&mut self) {
+        let expired = self.hint.as_ref().is_some_and(|x| {
+            x.shown_at.elapsed() > HINT_DURATION || x.board != self.game.get_visible_board()
+        });
+
+        if expired {
+            self.hint = None;
+        }
+    }
+
+    pub fn handle_mouse_press(&mut self, button: MouseButton) {
+        if self.editor.as_ref().is_some_and(|x| x.capturing) {
+            self.capture_input(Input::Mouse(button));
+            return;
+        }
+
+        if !self.mouse_down.contains(&button) {
+            self.mouse_down.push(button);
+        }
+    }
+
+    pub fn handle_key_press(&mut self, key: Key, window: &mut PistonWindow) {
+        // typed characters arrive through handle_text
+        if let Some(ref mut name) = self.name_prompt {
+            match key {
+                Key::Backspace => {
+                    name.pop();
+                }
+                Key::Return => self.submit_high_score(),
+                _ => (),
+            }
+            return;
+        }
+
+        if self.editor.is_some() && self.handle_editor_key(key) {
+            return;
+        }
+
+        if let Some(command) = self.bindings.get(Input::Key(key)) {
+            self.run_command(command, window);
+        }
+    }
+
+    // whether the command can be used on this board, networked boards come from the host,
+    // a shared board can not be paused, and neither can be undone
+    fn is_allowed(&self, command: Command) -> bool {
+        match command {
+            Command::Reset => self.race.is_none(),
+            Command::Pause => self.coop.is_none(),
+            Command::Undo => {
+                self.race.is_none()
+                    && self.coop.is_none()
+                    && self.daily.is_none()
+                    && self.tutorial.is_none()
+            }
+            Command::Hint => {
+                self.race.is_none() && self.daily.is_none() && self.tutorial.is_none()
+            }
+            Command::NextPreset | Command::PreviousPreset => {
+                self.race.is_none() && self.coop.is_none()
+            }
+            // a file's puzzles are played at their own size
+            Command::Preset(_) => {
+                let from_file = self.puzzles.as_ref().is_some_and(|x| match x.source {
+                    PuzzleSource::File(_) => true,
+                    PuzzleSource::Generated(_) => false,
+                });
+                self.race.is_none() && self.coop.is_none() && self.tutorial.is_none() && !from_file
+            }
+            _ => true,
+        }
+    }
+
+    fn run_command(&mut self, command: Command, window: &mut PistonWindow) {
+        if !self.is_allowed(command) {
+            return;
+        }
+
+        let cursor = self.cursor;
+
+        match command {
+            Command::Reveal => self.handle_cursor_action(&Action::Reveal(cursor)),
+            Command::Flag => self.handle_cursor_action(&Action::Flag(cursor)),
+            Command::Chord => self.handle_cursor_action(&Action::Chord(cursor)),
+            Command::Reset => self.reset_game(window),
+            Command::Pause => {
+                if self.game.is_paused() {
+                    self.game.resume();
+                } else {
+                    self.game.pause();
+                }
+            }
+            Command::Undo => {
+                self.game.undo();
+            }
+            Command::Hint => self.show_hint(),
+            Command::Scores => self.show_scores = !self.show_scores,
+            Command::Skin => {
+                let index = (self.skin + 1) % self.skins.len();
+                self.select_skin(index);
+            }
+            Command::Palette => {
+                self.palette = (self.palette + 1) % PALETTES.len();
+                self.update_theme();
+                self.save_colors();
+            }
+            Command::HighContrast => {
+                self.high_contrast = !self.high_contrast;
+                self.update_theme();
+                self.save_colors();
+            }
+            Command::Patterns => {
+                self.patterns = !self.patterns;
+                self.save_colors();
+            }
+            Command::Export => self.export_board(),
+            Command::Bindings => {
+                self.editor = Some(BindingEditor {
+                    selected: 0,
+                    capturing: false,
+                });
+            }
+            Command::ZoomIn => self.step_zoom(ZOOM_STEP, window),
+            Command::ZoomOut => self.step_zoom(-ZOOM_STEP, window),
+            Command::ZoomReset => {
+                self.zoom = 1.0;
+                self.resize_window(window);
+            }
+            // puzzles and lessons are gone through in order instead
+            Command::NextPreset if self.puzzles.is_some() => self.step_puzzle(1, window),
+            Command::PreviousPreset if self.puzzles.is_some() => self.step_puzzle(-1, window),
+            Command::NextPreset if self.tutorial.is_some() => self.step_lesson(1, window),
+            Command::PreviousPreset if self.tutorial.is_some() => self.step_lesson(-1, window),
+            Command::NextPreset => {
+                let index = self.presets.get_next(self.preset);
+                self.select_preset(index, window);
+            }
+            Command::PreviousPreset => {
+                let index = self.presets.get_previous(self.preset);
+                self.select_preset(index, window);
+            }
+            Command::Preset(index) => self.select_preset(index, window),
+            Command::CursorUp => self.move_cursor(-1, 0),
+            Command::CursorDown => self.move_cursor(1, 0),
+            Command::CursorLeft => self.move_cursor(0, -1),
+            Command::CursorRight => self.move_cursor(0, 1),
+        }
+    }
+
+    fn step_zoom(&mut self, step: f64, window: &mut PistonWindow) {
+        // steps land on multiples of the step even after a resize by hand
+        self.zoom = ((self.zoom + step) / ZOOM_STEP).round() * ZOOM_STEP;
+        self.resize_window(window);
+    }
+
+    // up and down pick a command, enter binds the next key or mouse button pressed to it,
+    // backspace puts back its defaults, M swaps the mouse buttons, and the key that opened the
+    // screen closes it, returns whether the key was used
+    fn handle_editor_key(&mut self, key: Key) -> bool {
+        let editor = match self.editor {
+            Some(ref mut editor) => editor,
+            None => return false,
+        };
+
+        if editor.capturing {
+            self.capture_input(Input::Key(key));
+            return true;
+        }
+
+        match key {
+            Key::Up => editor.selected = editor.selected.saturating_sub(1),
+            Key::Down => editor.selected = min(editor.selected + 1, COMMANDS.len() - 1),
+            Key::Return => editor.capturing = true,
+            Key::Backspace => {
+                let command = COMMANDS[editor.selected].1;
+                self.bindings.reset(command);
+                self.save_bindings();
+            }
+            Key::M => {
+                self.bindings.swap_mouse_buttons();
+                self.left_handed = !self.left_handed;
+                self.save_bindings();
+            }
+            _ => {
+                if self.bindings.get(Input::Key(key)) == Some(Command::Bindings) {
+                    self.editor = None;
+                }
+            }
+        }
+
+        true
+    }
+
+    // the input stops running whatever it ran before, except the bindings key which gives up
+    // so the screen can always be closed
+    fn capture_input(&mut self, input: Input) {
+        let command = match self.editor {
+            Some(ref mut editor) => {
+                editor.capturing = false;
+                COMMANDS[editor.selected].1
+            }
+            None => return,
+        };
+
+        if self.bindings.get(input) == Some(Command::Bindings) {
+            return;
+        }
+
+        self.bindings.set(command, &[input]);
+        self.save_bindings();
+    }
+
+    // an SVG of the board in the working directory, flags on safe squares crossed out once
+    // the game is over
+    fn export_board(&self) {
+        let options = RenderOptions {
+            palette: PALETTES[self.palette],
+            high_contrast: self.high_contrast,
+            patterns: self.patterns,
+            misflags: self.game.state != GameState::Ongoing,
+            ..RenderOptions::default()
+        };
+        let name = format!("minesweep-{}.svg", Local::now().format("%Y%m%d-%H%M%S"));
+        let path = Path::new(&name);
+
+        match svg::save_svg(&self.game, &options, path) {
+            Ok(()) => println!("exported board to {}", path.display()),
+            Err(err) => eprintln!("could not export board to {}: {}", path.display(), err),
+        }
+    }
+
+    // writes the bindings back to the config, leaving the rest of the file as it is
+    fn save_bindings(&self) {
+        let keys = self.bindings.get_config(self.left_handed);
+
+        if let Some(ref path) = self.config_path {
+            if let Err(err) = Config::save_keys(path, &keys, self.left_handed) {
+                eprintln!("could not save config to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    // writes the color settings back to the config, leaving the rest of the file as it is
+    fn save_colors(&self) {
+        let palette = match self.palette {
+            0 => None,
+            index => Some(PALETTES[index].name),
+        };
+
+        if let Some(ref path) = self.config_path {
+            if let Err(err) =
+                Config::save_colors(path, palette, self.high_contrast, self.patterns)
+            {
+                eprintln!("could not save config to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    fn get_editor_lines(&self, editor: &BindingEditor) -> Vec<String> {
+        let mut lines = vec![
+            "BINDINGS".to_owned(),
+            if editor.capturing {
+                "press a key or button".to_owned()
+            } else {
+                "enter bind  bksp default  m swap mouse".to_owned()
+            },
+            String::new(),
+        ];
+
+        // only as many commands as fit on the board, scrolled to keep the selected one in view
+        let rows = (f64::from(self.game.rows * SQUARE_SIZE) / SCORES_LINE_HEIGHT) as usize;
+        let rows = rows.saturating_sub(lines.len() + 1).max(1);
+        let first = (editor.selected + 1).saturating_sub(rows);
+
+        for (i, &(name, command)) in COMMANDS.iter().enumerate().skip(first).take(rows) {
+            let inputs: Vec<_> = self
+                .bindings
+                .get_inputs(command)
+                .into_iter()
+                .map(bindings::get_input_name)
+                .collect();
+            let marker = if i == editor.selected { ">" } else { " " };
+
+            lines.push(format!("{} {:<15} {}", marker, name, inputs.join(" ")));
+        }
+
+        lines
+    }
+
+    fn get_time_text(&mut self) -> String {
+        // no clock to race against in a puzzle, its number instead
+        if let Some(ref puzzles) = self.puzzles {
+            return format!("#{:03}", puzzles.number + 1);
+        }
+        // nor in a lesson, which counts its steps
+        if let Some(ref tutorial) = self.tutorial {
+            let steps = tutorial.lessons[tutorial.lesson].steps.len();
+            return format!("{}/{}", min(tutorial.step + 1, steps), steps);
+        }
+
+        let exact = self.game.state != GameState::Ongoing;
+
+        match (self.game.lose_reason(), self.game.get_time_left_ms()) {
+            (Some(LoseReason::TimeUp), _) => "TIME".to_owned(),
+            (_, Some(time_left)) => render::format_time(time_left, exact),
+            (_, None) => render::format_time(self.game.elapsed_ms(), exact),
+        }
+    }
+
+    pub fn get_text_color(num: u32) -> [f32; 4] {
+        palette::get_number_color(num)
+    }
+
+    fn draw_hearts(&self, c: &Context, g: &mut G2d, glyphs: &mut Glyphs, x: f64, y: f64) {
+        let lobe = HEART_SIZE * 0.5;
+
+        ellipse(HEART_COLOR, [x, y, lobe, lobe], c.transform, g);
+        ellipse(HEART_COLOR, [x + lobe, y, lobe, lobe], c.transform, g);
+        polygon(
+            HEART_COLOR,
+            &[
+                [x + 0.2, y + lobe * 0.7],
+                [x + HEART_SIZE - 0.2, y + lobe * 0.7],
+                [x + lobe, y + HEART_SIZE],
+            ],
+            c.transform,
+            g,
+        );
+
+        let count_transform = c
+            .transform
+            .trans(x + HEART_SIZE, UI_FONT_Y_OFFSET - 2.0)
+            .zoom(0.5);
+
+        self.draw_text(
+            HEART_COLOR,
+            HEART_FONT_SIZE,
+            &self.game.get_lives_left().to_string(),
+            glyphs,
+            count_transform,
+            g,
+        );
+    }
+
+    fn draw_race_bars(
+        &self,
+        c: &Context,
+        g: &mut G2d,
+        glyphs: &mut Glyphs,
+        opponents: &[RaceStatus],
+    ) {
+        if opponents.is_empty() {
+            return;
+        }
+
+        let board_width = f64::from(self.game.cols * SQUARE_SIZE) - MARGIN;
+        let bar_width = board_width / opponents.len() as f64 - MARGIN;
+        let bar_height = f64::from(RACE_BAR_HEIGHT) - MARGIN;
+        let y = f64::from(TOP_BAR_HEIGHT);
+
+        for (i, opponent) in opponents.iter().enumerate() {
+            let x = MARGIN + (bar_width + MARGIN) * i as f64;
+            let fill_color = match opponent.state {
+                GameState::Ongoing => RACE_ONGOING_COLOR,
+                GameState::Won => RACE_WON_COLOR,
+                GameState::Lost => self.theme.mine_revealed,
+            };
+
+            rectangle::Rectangle::new(self.theme.ui_rect).draw(
+                [x, y, bar_width, bar_height],
+                &Default::default(),
+                c.transform,
+                g,
+            );
+            rectangle::Rectangle::new(fill_color).draw(
+                [
+                    x,
+                    y,
+                    bar_width * f64::from(min(opponent.progress, 100)) / 100.0,
+                    bar_height,
+                ],
+                &Default::default(),
+                c.transform,
+                g,
+            );
+
+            let label = if opponent.explosions > 0 {
+                format!("{} {}% x{}", opponent.name, opponent.progress, opponent.explosions)
+            } else {
+                format!("{} {}%", opponent.name, opponent.progress)
+            };
+
+            self.draw_text(
+                RACE_TEXT_COLOR,
+                RACE_FONT_SIZE,
+                &label,
+                glyphs,
+                c.transform.trans(x + MARGIN, y + bar_height - 3.0).zoom(0.5),
+                g,
+            );
+        }
+    }
+
+    fn get_scores_lines(&self) -> Vec<String> {
+        let key = self.get_scores_key();
+        let mut lines = vec![key.to_uppercase(), String::new()];
+
+        let entries = self.high_scores.get_entries(&key);
+        if entries.is_empty() {
+            lines.push("no wins yet".to_owned());
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            // wins helped along by hints get a star by the time
+            lines.push(format!(
+                "{:>2} {:>7}{} {:>5.1} {} {}",
+                i + 1,
+                render::format_time(entry.time_ms, true),
+                if entry.hints > 0 { '*' } else { ' ' },
+                entry.bbbv_per_sec,
+                entry.name,
+                entry.date
+            ));
+        }
+
+        lines
+    }
+
+    // covers the board with a panel of text lines, cut short to fit narrow boards
+    fn draw_overlay_text(
+        &self,
+        c: &Context,
+        g: &mut G2d,
+        glyphs: &mut Glyphs,
+        top_bar_height: u32,
+        lines: &[String],
+    ) {
+        let board_width = f64::from(self.game.cols * SQUARE_SIZE);
+        let board_height = f64::from(self.game.rows * SQUARE_SIZE);
+        let max_chars = ((board_width - MARGIN * 4.0) / SCORES_CHAR_WIDTH) as usize;
+
+        rectangle::Rectangle::new(PAUSE_OVERLAY_COLOR).draw(
+            [0.0, f64::from(top_bar_height), board_width, board_height],
+            &Default::default(),
+            c.transform,
+            g,
+        );
+
+        for (i, line) in lines.iter().enumerate() {
+            let line: String = line.chars().take(max_chars).collect();
+            let line_transform = c
+                .transform
+                .trans(
+                    MARGIN * 2.0,
+                    f64::from(top_bar_height) + SCORES_LINE_HEIGHT * (i + 1) as f64 + MARGIN,
+                )
+                .zoom(0.5);
+
+            self.draw_text(
+                self.theme.ui_text,
+                SCORES_FONT_SIZE,
+                &line,
+                glyphs,
+                line_transform,
+                g,
+            );
+        }
+    }
+
+    // outlines the square and the numbers it follows from, with the reasoning on a panel over
+    // the half of the board away from the square
+    fn draw_hint(
+        &self,
+        c: &Context,
+        g: &mut G2d,
+        glyphs: &mut Glyphs,
+        board_transform: math::Matrix2d,
+        hint: &Hint,
+    ) {
+        let outline = |pos: &Position, color: types::Color, radius: f64, g: &mut G2d| {
+            rectangle::Rectangle::new_border(color, radius).draw(
+                [
+                    f64::from(pos.1 * SQUARE_SIZE) - MARGIN,
+                    f64::from(pos.0 * SQUARE_SIZE) - MARGIN,
+                    f64::from(SQUARE_SIZE),
+                    f64::from(SQUARE_SIZE),
+                ],
+                &Default::default(),
+                board_transform,
+                g,
+            );
+        };
+
+        for pos in &hint.sources {
+            outline(pos, HINT_SOURCE_COLOR, CURSOR_BORDER_RADIUS, g);
+        }
+        let color = match hint.kind {
+            HintKind::Safe => HINT_SAFE_COLOR,
+            HintKind::Mine => HINT_MINE_COLOR,
+            HintKind::Guess => HINT_GUESS_COLOR,
+        };
+        outline(&hint.pos, color, HINT_BORDER_RADIUS, g);
+
+        let top = f64::from(self.get_top_bar_height());
+        let board_width = f64::from(self.game.cols * SQUARE_SIZE);
+        let board_height = f64::from(self.game.rows * SQUARE_SIZE);
+        let max_chars = ((board_width - MARGIN * 4.0) / SCORES_CHAR_WIDTH) as usize;
+        let lines = wrap_text(&hint.explanation, max_chars);
+
+        let panel_height = SCORES_LINE_HEIGHT * lines.len() as f64 + MARGIN * 2.0;
+        let panel_y = if hint.pos.0 * 2 < self.game.rows {
+            top + board_height - panel_height
+        } else {
+            top
+        };
+        rectangle::Rectangle::new(PAUSE_OVERLAY_COLOR).draw(
+            [0.0, panel_y, board_width, panel_height],
+            &Default::default(),
+            c.transform,
+            g,
+        );
+
+        for (i, line) in lines.iter().enumerate() {
+            let line: String = line.chars().take(max_chars).collect();
+            let line_transform = c
+                .transform
+                .trans(
+                    MARGIN * 2.0,
+                    panel_y + SCORES_LINE_HEIGHT * (i + 1) as f64 - MARGIN,
+                )
+                .zoom(0.5);
+
+            self.draw_text(
+                self.theme.ui_text,
+                SCORES_FONT_SIZE,
+                &line,
+                glyphs,
+                line_transform,
+                g,
+            );
+        }
+    }
+
+    // outlines the squares the step points at, with its text under the board
+    fn draw_lesson(
+        &self,
+        c: &Context,
+        g: &mut G2d,
+        glyphs: &mut Glyphs,
+        board_transform: math::Matrix2d,
+    ) {
+        let tutorial = match self.tutorial {
+            Some(ref tutorial) => tutorial,
+            None => return,
+        };
+
+        if let Some(step) = tutorial.lessons[tutorial.lesson].steps.get(tutorial.step) {
+            for pos in &step.marks {
+                rectangle::Rectangle::new_border(LESSON_MARK_COLOR, HINT_BORDER_RADIUS).draw(
+                    [
+                        f64::from(pos.1 * SQUARE_SIZE) - MARGIN,
+                        f64::from(pos.0 * SQUARE_SIZE) - MARGIN,
+                        f64::from(SQUARE_SIZE),
+                        f64::from(SQUARE_SIZE),
+                    ],
+                    &Default::default(),
+                    board_transform,
+                    g,
+                );
+            }
+        }
+
+        let width = f64::from(self.get_base_size()[0]);
+        let top = f64::from(self.get_top_bar_height() + self.game.rows * SQUARE_SIZE);
+        rectangle::Rectangle::new(PAUSE_OVERLAY_COLOR).draw(
+            [0.0, top, width, f64::from(self.get_lesson_panel_height())],
+            &Default::default(),
+            c.transform,
+            g,
+        );
+
+        for (i, line) in self.get_lesson_lines().iter().enumerate() {
+            let line_transform = c
+                .transform
+                .trans(
+                    MARGIN * 2.0,
+                    top + MARGIN + SCORES_LINE_HEIGHT * (i + 1) as f64 - MARGIN,
+                )
+                .zoom(0.5);
+
+            self.draw_text(
+                self.theme.ui_text,
+                SCORES_FONT_SIZE,
+                line,
+                glyphs,
+                line_transform,
+                g,
+            );
+        }
+    }
+
+    fn draw_face_button(
+        &mut self,
+        c: &Context,
+        g: &mut G2d,
+        x: f64,
+        y: f64,
+        icon_width: f64,
+        icon_height: f64,
+    ) {
+        let pressed = self.is_mouse_held(Command::Reveal) && self.face_selected;
+
+        self.face_button_rect = [
+            x - MARGIN,
+            y - MARGIN,
+            icon_width + MARGIN * 2.0,
+            icon_height + MARGIN * 2.0,
+        ];
+
+        let rect = self.face_button_rect;
+        self.draw_raised(rect, pressed, c.transform, g);
+    }
+
+    // raised with light and shadow edges when the skin has a bevel, flat and revealed while pressed
+    fn draw_raised(&self, rect: [f64; 4], pressed: bool, transform: math::Matrix2d, g: &mut G2d) {
+        if pressed || !self.theme.bevel {
+            let color = if pressed {
+                self.theme.cell_revealed
+            } else {
+                self.theme.cell_covered
+            };
+
+            rectangle::Rectangle::new_border(self.theme.cell_border, 1.0)
+                .color(color)
+                .draw(rect, &Default::default(), transform, g);
+            return;
+        }
+
+        let [x, y, width, height] = rect;
+        rectangle(self.theme.cell_covered, rect, transform, g);
+        rectangle(
+            self.theme.bevel_shadow,
+            [x, y + height - BEVEL_WIDTH, width, BEVEL_WIDTH],
+            transform,
+            g,
+        );
+        rectangle(
+            self.theme.bevel_shadow,
+            [x + width - BEVEL_WIDTH, y, BEVEL_WIDTH, height],
+            transform,
+            g,
+        );
+        rectangle(
+            self.theme.bevel_light,
+            [x, y, width - BEVEL_WIDTH, BEVEL_WIDTH],
+            transform,
+            g,
+        );
+        rectangle(
+            self.theme.bevel_light,
+            [x, y, BEVEL_WIDTH, height - BEVEL_WIDTH],
+            transform,
+            g,
+        );
+    }
+
+    // covered cells use the skin's texture when it has one
+    fn draw_covered_cell(
+        &self,
+        icons: &Icons,
+        rect: [f64; 4],
+        pressed: bool,
+        transform: math::Matrix2d,
+        g: &mut G2d,
+    ) {
+        let texture = if pressed {
+            &icons.cell_revealed
+        } else {
+            &icons.cell_covered
+        };
+
+        match *texture {
+            Some(ref texture) => Gui::draw_stretched(texture, rect, transform, g),
+            None => self.draw_raised(rect, pressed, transform, g),
+        }
+    }
+
+    fn draw_stretched(
+        texture: &G2dTexture,
+        rect: [f64; 4],
+        transform: math::Matrix2d,
+        g: &mut G2d,
+    ) {
+        let (width, height) = texture.get_size();
+        let transform = transform.trans(rect[0], rect[1]).scale(
+            rect[2] / f64::from(width.max(1)),
+            rect[3] / f64::from(height.max(1)),
+        );
+
+        image(texture, transform, g);
+    }
+
+    // a cross over exploded mines and a notch in the corner of flags, for telling them apart
+    // without color
+    fn draw_pattern(square: &Square, rect: [f64; 4], transform: math::Matrix2d, g: &mut G2d) {
+        let [x, y, w, h] = rect;
+
+        if square.exploded {
+            line(PATTERN_COLOR, PATTERN_LINE_RADIUS, [x, y, x + w, y + h], transform, g);
+            line(PATTERN_COLOR, PATTERN_LINE_RADIUS, [x + w, y, x, y + h], transform, g);
+        } else if square.state == SquareState::Flagged {
+            polygon(
+                PATTERN_COLOR,
+                &[[x, y], [x + PATTERN_SIZE, y], [x, y + PATTERN_SIZE]],
+                transform,
+                g,
+            );
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        window: &mut PistonWindow,
+        event: &Event,
+        glyphs: &mut Glyphs,
+        icons: &Icons,
+    ) {
+        let top_bar_height = self.get_top_bar_height();
+        let opponents = self
+            .race
+            .as_ref()
+            .map(|client| client.get_opponents())
+            .unwrap_or_default();
+
+        let window_size = window.size();
+        let draw_size = window.draw_size();
+        self.dpi = f64::from(draw_size.width) / f64::from(window_size.width.max(1));
+
+        window.draw_2d(event, |c, g| {
+            clear(self.theme.background, g);
+
+            // from here on everything is laid out unscaled
+            let c = c.trans(self.origin[0], self.origin[1]).zoom(self.scale);
+            let lesson_c = c;
+            let c = c.trans(self.get_board_offset(), 0.0);
+
+            let flags_rect = [
+                MARGIN,
+                MARGIN,
+                f64::from(UI_FONT_SIZE) * 1.15,
+                UI_RECT_HEIGHT,
+            ];
+            rectangle::Rectangle::new(self.theme.ui_rect).draw(
+                flags_rect,
+                &Default::default(),
+                c.transform,
+                g,
+            );
+
+            // mine hunt puts the scores where the flags and the time usually are
+            let scores = self.game.get_scores();
+            let (time_text, time_color) = if self.game.rules.mine_hunt {
+                (format!("{:03}", scores[1]), HUNT_PLAYER_COLORS[1])
+            } else {
+                (self.get_time_text(), self.theme.ui_text)
+            };
+            let time_text_width = glyphs.width(TIME_FONT_SIZE, &time_text).unwrap() * 0.5;
+            let time_rect_width = TIME_RECT_MIN_WIDTH.max(time_text_width + MARGIN * 4.0);
+            let time_rect = [
+                f64::from(self.game.cols * SQUARE_SIZE) - time_rect_width - MARGIN,
+                MARGIN,
+                time_rect_width,
+                UI_RECT_HEIGHT,
+            ];
+            rectangle::Rectangle::new(self.theme.ui_rect).draw(
+                time_rect,
+                &Default::default(),
+                c.transform,
+                g,
+            );
+
+            // outline whoever is to play, or whoever won once the hunt is over
+            let highlighted = match self.game.state {
+                GameState::Ongoing => Some(self.game.get_turn()),
+                _ => self.game.get_winner(),
+            };
+
+            if let (true, Some(player)) = (self.game.rules.mine_hunt, highlighted) {
+                let rect = if player == 0 { flags_rect } else { time_rect };
+                rectangle::Rectangle::new_border(
+                    HUNT_PLAYER_COLORS[player],
+                    HUNT_TURN_BORDER_RADIUS,
+                ).draw(rect, &Default::default(), c.transform, g);
+            }
+
+            // hard coded 2 pixel offset
+            let board_transform = c.transform.trans(2.0, 2.0 + f64::from(top_bar_height));
+
+            let face_icon = match self.game.state {
+                GameState::Ongoing => &icons.ongoing_face,
+                GameState::Won => &icons.win_face,
+                GameState::Lost => &icons.lost_face,
+            };
+            let (icon_width, icon_height) = face_icon.get_size();
+            let face_scale = FACE_ICON_SIZE / f64::from(icon_width.max(1));
+            let face_width = FACE_ICON_SIZE;
+            let face_height = f64::from(icon_height) * face_scale;
+            let face_x = f64::from(self.game.cols * SQUARE_SIZE) * 0.5 - face_width * 0.5;
+            let face_y = f64::from(TOP_BAR_HEIGHT) * 0.5 - face_height * 0.5;
+
+            let face_transform = c.transform.trans(face_x, face_y).zoom(face_scale);
+
+            // draw face button
+            self.draw_face_button(&c, g, face_x, face_y, face_width, face_height);
+
+            // render all triangles first in batch
+            for i in 0..self.game.rows {
+                for j in 0..self.game.cols {
+                    let curr_x = j * SQUARE_SIZE;
+                    let curr_y = i * SQUARE_SIZE;
+
+                    let curr_square = self.game.get_square(i, j);
+                    let cell_rect = [
+                        f64::from(curr_x),
+                        f64::from(curr_y),
+                        f64::from(SQUARE_SIZE) - 4.0,
+                        f64::from(SQUARE_SIZE) - 4.0,
+                    ];
+
+                    match curr_square.state {
+                        SquareState::Covered => {
+                            let pressed = self.is_mouse_held(Command::Reveal)
+                                && self.selected_position.is_some()
+                                && Position(i, j) == self.selected_position.unwrap();
+
+                            self.draw_covered_cell(icons, cell_rect, pressed, board_transform, g);
+                        }
+                        SquareState::Revealed => {
+                            let rect = if let Some(player) = curr_square.claimed_by {
+                                rectangle::Rectangle::new(HUNT_PLAYER_COLORS[player])
+                            } else if curr_square.is_mine {
+                                rectangle::Rectangle::new(self.theme.mine_border).border(Border {
+                                    color: self.theme.mine_revealed,
+                                    radius: 1.0,
+                                })
+                            } else if let Some(ref texture) = icons.cell_revealed {
+                                Gui::draw_stretched(texture, cell_rect, board_transform, g);
+                                continue;
+                            } else {
+                                rectangle::Rectangle::new(self.theme.cell_border).border(Border {
+                                    color: self.theme.cell_revealed,
+                                    radius: 1.0,
+                                })
+                            };
+
+                            rect.draw(cell_rect, &Default::default(), board_transform, g);
+                        }
+                        SquareState::Flagged => {
+                            if curr_square.exploded {
+                                rectangle::Rectangle::new(self.theme.mine_border)
+                                    .border(Border {
+                                        color: self.theme.mine_revealed,
+                                        radius: 1.0,
+                                    })
+                                    .draw(cell_rect, &Default::default(), board_transform, g);
+                            } else {
+                                self.draw_covered_cell(icons, cell_rect, false, board_transform, g);
+                            }
+                        }
+                    }
+
+                    if self.patterns {
+                        Gui::draw_pattern(curr_square, cell_rect, board_transform, g);
+                    }
+                }
+            }
+
+            // then render all texts and images in batch
+            for i in 0..self.game.rows {
+                for j in 0..self.game.cols {
+                    let curr_x = j * SQUARE_SIZE;
+                    let curr_y = i * SQUARE_SIZE;
+
+                    let curr_square = self.game.get_square(i, j);
+
+                    let text_transform = board_transform
+                        .trans(
+                            f64::from(curr_x) + f64::from(SQUARE_SIZE) * 0.19,
+                            f64::from(curr_y) + f64::from(SQUARE_SIZE) * 0.65,
+                        )
+                        .zoom(0.5);
+
+                    let mine_transform = board_transform
+                        .trans(
+                            f64::from(curr_x) + f64::from(SQUARE_SIZE) * 0.06,
+                            f64::from(curr_y) + f64::from(SQUARE_SIZE) * 0.06,
+                        )
+                        .zoom(MINE_ICON_SIZE / f64::from(icons.mine.get_width().max(1)));
+
+                    match curr_square.state {
+                        SquareState::Revealed => {
+                            if curr_square.is_mine {
+                                image(&icons.mine, mine_transform, g);
+                            }
+
+                            if !curr_square.is_mine && curr_square.adjacent_mines > 0 {
+                                self.draw_text(
+                                    self.theme.get_number_color(curr_square.adjacent_mines),
+                                    NUMBER_FONT_SIZE,
+                                    &curr_square.adjacent_mines.to_string(),
+                                    glyphs,
+                                    text_transform,
+                                    g,
+                                );
+                            }
+                        }
+                        SquareState::Flagged => {
+                            let flag_transform = board_transform
+                                .trans(
+                                    f64::from(curr_x) + f64::from(SQUARE_SIZE) * 0.085,
+                                    f64::from(curr_y) + f64::from(SQUARE_SIZE) * 0.085,
+                                )
+                                .zoom(FLAG_ICON_SIZE / f64::from(icons.flag.get_width().max(1)));
+
+                            image(&icons.flag, flag_transform, g);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            for (id, pos) in &self.cursors {
+                if self.coop.as_ref().map(|client| client.id) == Some(*id) {
+                    continue;
+                }
+
+                let color = CURSOR_COLORS[*id as usize % CURSOR_COLORS.len()];
+                rectangle::Rectangle::new_border(color, CURSOR_BORDER_RADIUS).draw(
+                    [
+                        f64::from(pos.1 * SQUARE_SIZE) - MARGIN,
+                        f64::from(pos.0 * SQUARE_SIZE) - MARGIN,
+                        f64::from(SQUARE_SIZE),
+                        f64::from(SQUARE_SIZE),
+                    ],
+                    &Default::default(),
+                    board_transform,
+                    g,
+                );
+            }
+
+            if self.show_cursor {
+                rectangle::Rectangle::new_border(KEYBOARD_CURSOR_COLOR, CURSOR_BORDER_RADIUS).draw(
+                    [
+                        f64::from(self.cursor.1 * SQUARE_SIZE) - MARGIN,
+                        f64::from(self.cursor.0 * SQUARE_SIZE) - MARGIN,
+                        f64::from(SQUARE_SIZE),
+                        f64::from(SQUARE_SIZE),
+                    ],
+                    &Default::default(),
+                    board_transform,
+                    g,
+                );
+            }
+
+            if let Some(ref shown) = self.hint {
+                self.draw_hint(&c, g, glyphs, board_transform, &shown.hint);
+            }
+
+            if self.tutorial.is_some() {
+                self.draw_lesson(&lesson_c, g, glyphs, board_transform);
+            }
+
+            // cover the whole board so it can not be studied while the clock is stopped
+            if self.game.is_paused() {
+                let board_width = f64::from(self.game.cols * SQUARE_SIZE);
+                let board_height = f64::from(self.game.rows * SQUARE_SIZE);
+
+                rectangle::Rectangle::new(PAUSE_OVERLAY_COLOR).draw(
+                    [0.0, f64::from(top_bar_height), board_width, board_height],
+                    &Default::default(),
+                    c.transform,
+                    g,
+                );
+
+                let pause_transform = c
+                    .transform
+                    .trans(
+                        board_width * 0.5 - 36.0,
+                        f64::from(top_bar_height) + board_height * 0.5 + 7.0,
+                    )
+                    .zoom(0.5);
+
+                self.draw_text(
+                    self.theme.ui_text,
+                    UI_FONT_SIZE,
+                    "PAUSED",
+                    glyphs,
+                    pause_transform,
+                    g,
+                );
+            }
+
+            if let Some(ref name) = self.name_prompt {
+                let lines = vec![
+                    "NEW HIGH SCORE".to_owned(),
+                    String::new(),
+                    format!("name: {}_", name),
+                    String::new(),
+                    "enter to save".to_owned(),
+                ];
+                self.draw_overlay_text(&c, g, glyphs, top_bar_height, &lines);
+            } else if let Some(ref editor) = self.editor {
+                let lines = self.get_editor_lines(editor);
+                self.draw_overlay_text(&c, g, glyphs, top_bar_height, &lines);
+            } else if self.show_scores {
+                let lines = self.get_scores_lines();
+                self.draw_overlay_text(&c, g, glyphs, top_bar_height, &lines);
+            }
+
+            let flag_num_transform = c.transform.trans(3.5, UI_FONT_Y_OFFSET).zoom(0.5);
+
+            let (flags_text, flags_color) = if self.game.rules.mine_hunt {
+                (format!("{:03}", scores[0]), HUNT_PLAYER_COLORS[0])
+            } else {
+                (format!("{:03}", self.game.get_flags_left()), self.theme.ui_text)
+            };
+
+            self.draw_text(
+                flags_color,
+                UI_FONT_SIZE,
+                &flags_text,
+                glyphs,
+                flag_num_transform,
+                g,
+            );
+
+            if self.game.rules.lives > 1 {
+                let hearts_x = MARGIN + 1.0 + f64::from(UI_FONT_SIZE) * 1.15;
+                let hearts_y = f64::from(TOP_BAR_HEIGHT) * 0.5 - HEART_SIZE * 0.5;
+                self.draw_hearts(&c, g, glyphs, hearts_x, hearts_y);
+            }
+
+            self.draw_race_bars(&c, g, glyphs, &opponents);
+
+            image(face_icon, face_transform, g);
+
+            // centered in the time box, which grows for the exact time at game end
+            let time_transform = c
+                .transform
+                .trans(
+                    f64::from(self.game.cols * SQUARE_SIZE)
+                        - MARGIN
+                        - (time_rect_width + time_text_width) * 0.5,
+                    UI_FONT_Y_OFFSET - 2.0,
+                )
+                .zoom(0.5);
+
+            self.draw_text(
+                time_color,
+                TIME_FONT_SIZE,
+                &time_text,
+                glyphs,
+                time_transform,
+                g,
+            );
+        });
+    }
+}
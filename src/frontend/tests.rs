@@ -0,0 +1,100 @@
+use super::*;
+
+fn assert_fit(fit: (f64, [f64; 2]), scale: f64, origin: [f64; 2]) {
+    assert!((fit.0 - scale).abs() < 1e-9, "scale {} != {}", fit.0, scale);
+    assert!((fit.1[0] - origin[0]).abs() < 1e-9, "x {} != {}", fit.1[0], origin[0]);
+    assert!((fit.1[1] - origin[1]).abs() < 1e-9, "y {} != {}", fit.1[1], origin[1]);
+}
+
+#[test]
+fn test_fit() {
+    assert_fit(get_fit(200, 100, 200, 100), 1.0, [0.0, 0.0]);
+    assert_fit(get_fit(400, 200, 200, 100), 2.0, [0.0, 0.0]);
+
+    // the narrower side decides, the other is centered
+    assert_fit(get_fit(300, 300, 200, 100), 1.5, [0.0, 75.0]);
+    assert_fit(get_fit(600, 100, 200, 100), 1.0, [200.0, 0.0]);
+
+    // sizes that do not divide evenly
+    assert_fit(get_fit(401, 203, 200, 100), 2.005, [0.0, 1.25]);
+    assert_fit(get_fit(150, 90, 200, 100), 0.75, [0.0, 7.5]);
+    assert_fit(get_fit(333, 250, 200, 100), 1.665, [0.0, 41.75]);
+}
+
+#[test]
+fn test_square_at() {
+    // a 3x2 board is 60x40 unscaled
+    assert_eq!(get_square_at(0.0, 0.0, 3, 2), Some(Position(0, 0)));
+    assert_eq!(get_square_at(19.9, 20.0, 3, 2), Some(Position(1, 0)));
+    assert_eq!(get_square_at(59.9, 39.9, 3, 2), Some(Position(1, 2)));
+
+    assert_eq!(get_square_at(-0.1, 0.0, 3, 2), None);
+    assert_eq!(get_square_at(0.0, -0.1, 3, 2), None);
+    assert_eq!(get_square_at(60.0, 0.0, 3, 2), None);
+    assert_eq!(get_square_at(0.0, 40.0, 3, 2), None);
+}
+
+#[test]
+fn test_window_point_to_square() {
+    let to_square = |x, y, width, height| {
+        let (scale, origin) = get_fit(width, height, 60, 40);
+        let (x, y) = unscale(x, y, scale, origin);
+        get_square_at(x, y, 3, 2)
+    };
+
+    // twice the size, every square is 40 pixels across
+    assert_eq!(to_square(39.0, 39.0, 120, 80), Some(Position(0, 0)));
+    assert_eq!(to_square(41.0, 41.0, 120, 80), Some(Position(1, 1)));
+    assert_eq!(to_square(119.0, 79.0, 120, 80), Some(Position(1, 2)));
+
+    // 1.7 times the size with 8 pixels of space above and below the board
+    assert_eq!(to_square(50.0, 7.0, 102, 84), None);
+    assert_eq!(to_square(50.0, 9.0, 102, 84), Some(Position(0, 1)));
+    assert_eq!(to_square(101.0, 75.0, 102, 84), Some(Position(1, 2)));
+    assert_eq!(to_square(101.0, 77.0, 102, 84), None);
+
+    // 1.25 times the size, centered sideways
+    assert_eq!(to_square(9.0, 25.0, 95, 50), None);
+    assert_eq!(to_square(11.0, 25.0, 95, 50), Some(Position(1, 0)));
+    assert_eq!(to_square(84.0, 24.0, 95, 50), Some(Position(0, 2)));
+}
+
+#[test]
+fn test_move_cursor() {
+    // a 4x3 board
+    let moved = |row, col, d_row, d_col| get_moved_cursor(Position(row, col), d_row, d_col, 4, 3);
+
+    assert_eq!(moved(1, 1, -1, 0), Some(Position(0, 1)));
+    assert_eq!(moved(1, 1, 1, 0), Some(Position(2, 1)));
+    assert_eq!(moved(1, 1, 0, -1), Some(Position(1, 0)));
+    assert_eq!(moved(1, 1, 0, 1), Some(Position(1, 2)));
+
+    // past each edge
+    assert_eq!(moved(0, 2, -1, 0), None);
+    assert_eq!(moved(2, 2, 1, 0), None);
+    assert_eq!(moved(1, 0, 0, -1), None);
+    assert_eq!(moved(1, 3, 0, 1), None);
+
+    // out of each corner, along either edge
+    assert_eq!(moved(0, 0, -1, 0), None);
+    assert_eq!(moved(0, 0, 0, -1), None);
+    assert_eq!(moved(0, 0, 0, 1), Some(Position(0, 1)));
+    assert_eq!(moved(0, 3, -1, 0), None);
+    assert_eq!(moved(0, 3, 0, 1), None);
+    assert_eq!(moved(2, 0, 1, 0), None);
+    assert_eq!(moved(2, 0, 0, -1), None);
+    assert_eq!(moved(2, 3, 1, 0), None);
+    assert_eq!(moved(2, 3, 0, 1), None);
+    assert_eq!(moved(2, 3, -1, 0), Some(Position(1, 3)));
+}
+
+#[test]
+fn test_clamp_cursor() {
+    assert_eq!(get_clamped_cursor(Position(1, 2), 4, 3), Position(1, 2));
+    assert_eq!(get_clamped_cursor(Position(2, 3), 4, 3), Position(2, 3));
+
+    // left outside a board that shrank
+    assert_eq!(get_clamped_cursor(Position(15, 1), 4, 3), Position(2, 1));
+    assert_eq!(get_clamped_cursor(Position(1, 29), 4, 3), Position(1, 3));
+    assert_eq!(get_clamped_cursor(Position(15, 29), 4, 3), Position(2, 3));
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{GameState, MineSweeper, Position, Rules, Square, SquareState};
+
+// a whole board written out one line per row, mines included
+//
+//   #  covered            *  covered mine
+//   .  revealed, or the number of mines around it
+//   F  flag on a safe square   M  flag on a mine
+//   @  revealed mine      X  exploded mine      E  exploded mine flagged with a life lost
+//
+//   ##*#
+//   F2##
+//   .1M#
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LayoutError {
+    // a board needs room for a mine and a safe first click
+    TooSmall,
+    // rows are numbered from 0 like positions
+    Ragged(u32),
+    UnknownSquare(char, Position),
+    WrongNumber(Position),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutError::TooSmall => write!(f, "board needs at least two squares"),
+            LayoutError::Ragged(row) => write!(f, "row {} is not as long as the first", row),
+            LayoutError::UnknownSquare(x, Position(i, j)) => {
+                write!(f, "unknown square {:?} at {} {}", x, i, j)
+            }
+            LayoutError::WrongNumber(Position(i, j)) => {
+                write!(f, "number at {} {} does not match the mines around it", i, j)
+            }
+        }
+    }
+}
+
+impl MineSweeper {
+    // the board exactly as written, blank lines and surrounding spaces are ignored, and the
+    // game is lost once the exploded mines use up the lives
+    pub fn from_layout(layout: &str, rules: Rules) -> Result<MineSweeper, LayoutError> {
+        let lines: Vec<&str> = layout
+            .lines()
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        let rows = lines.len() as u32;
+        let cols = lines.first().map_or(0, |x| x.chars().count()) as u32;
+        if rows * cols < 2 {
+            return Err(LayoutError::TooSmall);
+        }
+
+        let mut squares = HashMap::new();
+        let mut numbers = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() as u32 != cols {
+                return Err(LayoutError::Ragged(i as u32));
+            }
+
+            for (j, x) in line.chars().enumerate() {
+                let pos = Position(i as u32, j as u32);
+                let (is_mine, exploded, state) = match x {
+                    '#' => (false, false, SquareState::Covered),
+                    '*' => (true, false, SquareState::Covered),
+                    '.' => (false, false, SquareState::Revealed),
+                    '1'..='8' => {
+                        numbers.push((pos, x.to_digit(10).unwrap()));
+                        (false, false, SquareState::Revealed)
+                    }
+                    'F' => (false, false, SquareState::Flagged),
+                    'M' => (true, false, SquareState::Flagged),
+                    '@' => (true, false, SquareState::Revealed),
+                    'X' => (true, true, SquareState::Revealed),
+                    'E' => (true, true, SquareState::Flagged),
+                    _ => return Err(LayoutError::UnknownSquare(x, pos)),
+                };
+
+                squares.insert(pos, (is_mine, exploded, state));
+            }
+        }
+
+        let mines_index: Vec<usize> = (0..rows * cols)
+            .filter(|x| squares[&Position(x / cols, x % cols)].0)
+            .map(|x| x as usize)
+            .collect();
+        // the first safe covered square takes the mine a first click lands on
+        let covered = (false, false, SquareState::Covered);
+        let start_index = (0..rows * cols)
+            .find(|x| squares[&Position(x / cols, x % cols)] == covered)
+            .unwrap_or(0);
+
+        let mut game = MineSweeper::with_mines(cols, rows, mines_index, start_index, rules);
+        for (pos, square) in &mut game.map {
+            let (_, exploded, state) = squares[pos];
+            square.exploded = exploded;
+            square.state = state;
+        }
+
+        for &(pos, number) in &numbers {
+            if game.map[&pos].adjacent_mines != number {
+                return Err(LayoutError::WrongNumber(pos));
+            }
+        }
+
+        game.num_flagged = game
+            .map
+            .values()
+            .filter(|x| x.state == SquareState::Flagged)
+            .count() as u32;
+        game.explosions = game.map.values().filter(|x| x.exploded).count() as u32;
+        game.first_move = game
+            .map
+            .values()
+            .all(|x| x.state == SquareState::Covered);
+
+        // the state is worked out without the reveal that ends a game, which would rewrite it
+        game.state = if game.check_game_lost() {
+            GameState::Lost
+        } else if game.check_game_won() {
+            GameState::Won
+        } else {
+            GameState::Ongoing
+        };
+
+        Ok(game)
+    }
+
+    // the board with mines at the indices, counted row by row, and the squares set in the mask
+    // already revealed as if played up to there, so a first click never moves a mine
+    pub fn with_mask(
+        cols: u32,
+        rows: u32,
+        mines_index: Vec<usize>,
+        mask: &[bool],
+        rules: Rules,
+    ) -> MineSweeper {
+        if mask.len() != (rows * cols) as usize {
+            panic!("mask does not cover the board!");
+        }
+        if mines_index.iter().any(|&x| mask[x]) {
+            panic!("revealed mine!");
+        }
+
+        // any safe square not in the mask takes the mine a first click lands on
+        let start_index = (0..rows * cols)
+            .find(|&x| !mask[x as usize] && !mines_index.contains(&(x as usize)))
+            .unwrap_or(0);
+
+        let mut game = MineSweeper::with_mines(cols, rows, mines_index, start_index, rules);
+        for (index, _) in mask.iter().enumerate().filter(|x| *x.1) {
+            let pos = Position(index as u32 / cols, index as u32 % cols);
+            game.map.get_mut(&pos).unwrap().state = SquareState::Revealed;
+        }
+
+        if mask.iter().any(|&x| x) {
+            game.first_moved();
+        }
+        game.update_game_state();
+        game
+    }
+
+    // a board needs at least two squares, the mines and the start square stand in for the ones
+    // sampled from a seed
+    fn with_mines(
+        cols: u32,
+        rows: u32,
+        mines_index: Vec<usize>,
+        start_index: u32,
+        rules: Rules,
+    ) -> MineSweeper {
+        let mut game = MineSweeper::with_seed(cols, rows, 1, rules, 0);
+        game.num_mines = mines_index.len() as u32;
+        game.mines_index = mines_index;
+        game.start_index = start_index;
+        game.map.clear();
+        game.populate_board();
+        game
+    }
+
+    // reads back with from_layout to the same board
+    pub fn get_layout(&self) -> String {
+        (0..self.rows)
+            .map(|i| {
+                (0..self.cols)
+                    .map(|j| get_layout_char(self.get_square(i, j)))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn get_layout_char(square: &Square) -> char {
+    match (square.state, square.is_mine, square.exploded) {
+        (SquareState::Covered, false, _) => '#',
+        (SquareState::Covered, true, _) => '*',
+        (SquareState::Revealed, false, _) if square.adjacent_mines == 0 => '.',
+        (SquareState::Revealed, false, _) => {
+            ::std::char::from_digit(square.adjacent_mines, 10).unwrap()
+        }
+        (SquareState::Revealed, true, false) => '@',
+        (SquareState::Revealed, true, true) => 'X',
+        (SquareState::Flagged, false, _) => 'F',
+        (SquareState::Flagged, true, false) => 'M',
+        (SquareState::Flagged, true, true) => 'E',
+    }
+}
@@ -1,15 +1,21 @@
 #[cfg(test)]
 mod tests;
 
+pub mod clock;
+pub mod layout;
+pub mod preset;
+
 use std::cmp::min;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::time::SystemTime;
+use std::fmt;
+
+use rand::isaac::IsaacRng;
+use rand::{thread_rng, Rng, SeedableRng};
 
-use rand::seq::sample_indices;
-use rand::thread_rng;
-use rand::ThreadRng;
+use self::clock::{Clock, MonotonicClock};
+use self::preset::Preset;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum SquareState {
@@ -18,19 +24,83 @@ pub enum SquareState {
     Revealed,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameState {
     Ongoing,
     Won,
     Lost,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-pub enum Difficulty {
-    Beginner,
-    Intermediate,
-    Expert,
-    Custom,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoseReason {
+    MineHit,
+    TimeUp,
+}
+
+// the generator behind every seeded board, the same on every platform as it is seeded and
+// drawn from in fixed-width words
+pub fn get_seeded_rng(seed: u64) -> IsaacRng {
+    IsaacRng::from_seed(&[seed as u32, (seed >> 32) as u32][..])
+}
+
+// seconds the timer shows at most, no countdown can be longer
+pub const MAX_TIME: u64 = 9999;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rules {
+    // number of mine hits the player can take, 1 is the classic rule
+    pub lives: u32,
+    // countdown in seconds, the game is lost when it runs out
+    pub time_limit: Option<u64>,
+    // seconds added to the countdown for every opening cleared
+    pub bonus_seconds: u64,
+    // two players take turns and score by finding mines instead of avoiding them
+    pub mine_hunt: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            lives: 1,
+            time_limit: None,
+            bonus_seconds: 0,
+            mine_hunt: false,
+        }
+    }
+}
+
+// what the player can see of a square, hiding whatever is still covered
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum VisibleSquare {
+    Covered,
+    Flagged,
+    Revealed(u32),
+    Mine,
+    Exploded,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveError {
+    OutOfBounds,
+    GameOver,
+    Paused,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MoveError::OutOfBounds => write!(f, "position out of bounds"),
+            MoveError::GameOver => write!(f, "game is over"),
+            MoveError::Paused => write!(f, "game is paused"),
+        }
+    }
+}
+
+// a stretch of the game spent paused, at_ms is the game time when the pause began
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PauseInterval {
+    pub at_ms: u64,
+    pub duration_ms: u64,
 }
 
 // convention [row, col]
@@ -40,6 +110,9 @@ pub struct Position(pub u32, pub u32);
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Square {
     pub is_mine: bool,
+    pub exploded: bool,
+    // the mine hunt player who found this mine
+    pub claimed_by: Option<usize>,
     pub adjacent_mines: u32,
     pub state: SquareState,
 }
@@ -48,50 +121,96 @@ pub struct MineSweeper {
     pub cols: u32,
     pub rows: u32,
     pub num_mines: u32,
+    pub rules: Rules,
     num_flagged: u32,
-    rng: ThreadRng,
+    explosions: u32,
+    seed: u64,
     mines_index: Vec<usize>,
     map: HashMap<Position, Square>,
     first_move: bool,
-    timer: SystemTime,
+    clock: Box<dyn Clock + Send>,
+    started_at: u64,
     elapsed: u64,
+    paused_at: Option<u64>,
+    paused_ms: u64,
+    pauses: Vec<PauseInterval>,
+    bonus: u64,
+    scores: [u32; 2],
+    turn: usize,
+    undo_stack: Vec<Snapshot>,
+    undos: u32,
+    hints: u32,
     start_index: u32,
     pub state: GameState,
 }
 
+// everything a move can change, the clock keeps running through an undo
+#[derive(Clone)]
+struct Snapshot {
+    map: HashMap<Position, Square>,
+    mines_index: Vec<usize>,
+    num_flagged: u32,
+    explosions: u32,
+    bonus: u64,
+    scores: [u32; 2],
+    turn: usize,
+    first_move: bool,
+    state: GameState,
+}
+
 impl MineSweeper {
-    pub fn new_from_preset(difficulty: &Difficulty) -> MineSweeper {
-        match *difficulty {
-            Difficulty::Beginner => MineSweeper::new(8, 8, 10),
-            Difficulty::Intermediate => MineSweeper::new(16, 16, 40),
-            Difficulty::Expert => MineSweeper::new(24, 24, 99),
-            Difficulty::Custom => panic!("do not use this constructor with custom"),
-        }
+    pub fn new_from_preset(preset: &Preset, rules: Rules) -> MineSweeper {
+        MineSweeper::with_rules(preset.cols, preset.rows, preset.mines, rules)
     }
 
     pub fn new(cols: u32, rows: u32, num_mines: u32) -> MineSweeper {
-        let mut rng = thread_rng();
+        MineSweeper::with_rules(cols, rows, num_mines, Rules::default())
+    }
+
+    pub fn with_rules(cols: u32, rows: u32, num_mines: u32, rules: Rules) -> MineSweeper {
+        MineSweeper::with_seed(cols, rows, num_mines, rules, thread_rng().gen())
+    }
+
+    // the same seed and dimensions always lay out the same board
+    pub fn with_seed(
+        cols: u32,
+        rows: u32,
+        num_mines: u32,
+        rules: Rules,
+        seed: u64,
+    ) -> MineSweeper {
+        assert!(rules.lives > 0);
+        assert!(rules.time_limit.is_none_or(|limit| limit <= MAX_TIME));
 
         if rows * cols <= (num_mines - 1) {
             panic!("too many mines!");
         }
-        // samples one more position than max_mine
-        let mut mines_index =
-            sample_indices(&mut rng, (rows * cols) as usize, num_mines as usize + 1);
-        // then remove one to serve as start position, this guarantees start position will never have mines already
-        let start_index = mines_index.pop().unwrap() as u32;
+
+        let (mines_index, start_index) = MineSweeper::sample_mines(cols, rows, num_mines, seed);
 
         let mut game = MineSweeper {
             cols,
             rows,
             num_mines,
+            rules,
             num_flagged: 0,
+            explosions: 0,
             mines_index,
-            rng,
+            seed,
             map: HashMap::new(),
             first_move: true,
-            timer: SystemTime::now(),
+            clock: Box::new(MonotonicClock::new()),
+            started_at: 0,
             elapsed: 0,
+            paused_at: None,
+            paused_ms: 0,
+            pauses: Vec::new(),
+            bonus: 0,
+            scores: [0, 0],
+            turn: 0,
+            undo_stack: Vec::new(),
+            undos: 0,
+            hints: 0,
             start_index,
             state: GameState::Ongoing,
         };
@@ -102,34 +221,159 @@ impl MineSweeper {
     }
 
     pub fn reset(&mut self) {
+        let seed = thread_rng().gen();
+        self.reset_with_seed(seed);
+    }
+
+    pub fn reset_with_seed(&mut self, seed: u64) {
+        self.seed = seed;
         self.num_flagged = 0;
-        self.mines_index = sample_indices(
-            &mut self.rng,
-            (self.rows * self.cols) as usize,
-            self.num_mines as usize + 1,
-        );
-        self.start_index = self.mines_index.pop().unwrap() as u32;
+        self.explosions = 0;
+        self.bonus = 0;
+        self.scores = [0, 0];
+        self.turn = 0;
+        self.undo_stack.clear();
+        self.undos = 0;
+        self.hints = 0;
+        self.elapsed = 0;
+        self.paused_at = None;
+        self.paused_ms = 0;
+        self.pauses.clear();
+        let (mines_index, start_index) =
+            MineSweeper::sample_mines(self.cols, self.rows, self.num_mines, seed);
+        self.mines_index = mines_index;
+        self.start_index = start_index;
         self.map.clear();
         self.populate_board();
         self.state = GameState::Ongoing;
         self.first_move = true;
     }
 
+    fn sample_mines(cols: u32, rows: u32, num_mines: u32, seed: u64) -> (Vec<usize>, u32) {
+        let mut rng = get_seeded_rng(seed);
+        // samples one more position than max_mine, by a partial shuffle drawing only u32s so
+        // usize's width makes no difference
+        let mut mines_index: Vec<usize> = (0..(rows * cols) as usize).collect();
+        for i in 0..=num_mines {
+            let j = rng.gen_range(i, rows * cols);
+            mines_index.swap(i as usize, j as usize);
+        }
+        mines_index.truncate(num_mines as usize + 1);
+        // then remove one to serve as start position, this guarantees start position will never have mines already
+        let start_index = mines_index.pop().unwrap() as u32;
+
+        (mines_index, start_index)
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    // swaps the time source, meant to be called before the first move
+    pub fn set_clock(&mut self, clock: Box<dyn Clock + Send>) {
+        self.clock = clock;
+        self.started_at = self.clock.now_ms();
+    }
+
     pub fn first_moved(&mut self) {
-        if self.first_move {
-            self.timer = SystemTime::now();
+        if self.first_move && !self.is_paused() {
+            self.started_at = self.clock.now_ms();
             self.first_move = false;
         }
     }
 
+    // whether the first reveal has been made and the clock is running
+    pub fn is_started(&self) -> bool {
+        !self.first_move
+    }
+
+    // whole seconds for the timer display
     pub fn game_time(&mut self) -> u64 {
-        if self.first_move {
-            0
-        } else if self.state == GameState::Ongoing {
-            self.elapsed = min(self.timer.elapsed().unwrap().as_secs(), 9999);
-            self.elapsed
+        min(self.elapsed_ms() / 1000, MAX_TIME)
+    }
+
+    pub fn elapsed_ms(&mut self) -> u64 {
+        self.update_elapsed();
+        self.elapsed
+    }
+
+    // the finished game's time, None while it is still being played
+    pub fn final_time_ms(&self) -> Option<u64> {
+        if self.state == GameState::Ongoing {
+            None
+        } else {
+            Some(self.elapsed)
+        }
+    }
+
+    // seconds left on the countdown, None when playing without a time limit
+    pub fn get_time_left(&mut self) -> Option<u64> {
+        self.get_time_left_ms().map(|left| left.div_ceil(1000))
+    }
+
+    pub fn get_time_left_ms(&mut self) -> Option<u64> {
+        let elapsed = self.elapsed_ms();
+        self.time_limit_ms()
+            .map(|limit| limit.saturating_sub(elapsed))
+    }
+
+    pub fn lose_reason(&self) -> Option<LoseReason> {
+        if self.state != GameState::Lost {
+            None
+        } else if self.explosions >= self.rules.lives {
+            Some(LoseReason::MineHit)
         } else {
-            self.elapsed
+            Some(LoseReason::TimeUp)
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if self.state == GameState::Ongoing && self.paused_at.is_none() {
+            self.update_elapsed();
+            self.paused_at = Some(self.clock.now_ms());
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            // the timer has not started yet if paused before the first move
+            if !self.first_move {
+                let duration_ms = self.clock.now_ms().saturating_sub(paused_at);
+                self.paused_ms += duration_ms;
+                self.pauses.push(PauseInterval {
+                    at_ms: self.elapsed,
+                    duration_ms,
+                });
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn pause_intervals(&self) -> &[PauseInterval] {
+        &self.pauses
+    }
+
+    fn update_elapsed(&mut self) {
+        if !self.first_move && self.state == GameState::Ongoing {
+            let now = self.paused_at.unwrap_or_else(|| self.clock.now_ms());
+            self.elapsed = now.saturating_sub(self.started_at + self.paused_ms);
+        }
+    }
+
+    // bonus time stops adding up where the timer runs out of digits
+    fn time_limit_ms(&self) -> Option<u64> {
+        self.rules
+            .time_limit
+            .map(|limit| min(limit + self.bonus, MAX_TIME) * 1000)
+    }
+
+    fn time_expired(&self) -> bool {
+        match self.time_limit_ms() {
+            Some(limit) => !self.first_move && self.elapsed >= limit,
+            None => false,
         }
     }
 
@@ -141,6 +385,8 @@ impl MineSweeper {
                 Position(i, j),
                 Square {
                     is_mine: true,
+                    exploded: false,
+                    claimed_by: None,
                     adjacent_mines: 0,
                     state: SquareState::Covered,
                 },
@@ -160,6 +406,8 @@ impl MineSweeper {
                     .count() as u32;
                 self.map.entry(Position(i, j)).or_insert(Square {
                     is_mine: false,
+                    exploded: false,
+                    claimed_by: None,
                     adjacent_mines,
                     state: SquareState::Covered,
                 });
@@ -206,11 +454,13 @@ impl MineSweeper {
     }
 
     pub fn update_game_state(&mut self) {
+        self.update_elapsed();
+
         if self.check_game_won() {
             self.state = GameState::Won;
             self.map
                 .iter_mut()
-                .filter(|&(_, ref x)| x.state == SquareState::Covered)
+                .filter(|(_, x)| x.state == SquareState::Covered)
                 .for_each(|(_, ref mut x)| x.state = SquareState::Revealed);
         } else if self.check_game_lost() {
             self.state = GameState::Lost;
@@ -223,7 +473,11 @@ impl MineSweeper {
     }
 
     fn check_game_won(&self) -> bool {
-        if self.num_flagged < self.num_mines {
+        if self.rules.mine_hunt {
+            // the hunt is decided once a player holds the majority, or every mine is taken
+            let claimed = self.scores[0] + self.scores[1];
+            self.scores.iter().any(|x| x * 2 > self.num_mines) || claimed == self.num_mines
+        } else if self.num_flagged < self.num_mines {
             false
         } else {
             self.map
@@ -233,37 +487,42 @@ impl MineSweeper {
     }
 
     fn check_game_lost(&self) -> bool {
-        self.map
-            .values()
-            .any(|x| x.is_mine && x.state == SquareState::Revealed)
+        self.explosions >= self.rules.lives || self.time_expired()
     }
 
     pub fn toggle_flag_square(&mut self, curr_pos: &Position) {
         assert!(curr_pos.0 < self.rows);
         assert!(curr_pos.1 < self.cols);
 
-        if self.map[curr_pos].state == SquareState::Revealed {
-            return;
-        } else if self.map[curr_pos].state == SquareState::Flagged {
-            self.map.get_mut(curr_pos).unwrap().state = SquareState::Covered;
-            self.num_flagged -= 1;
-        } else if self.num_flagged >= self.num_mines {
+        // nothing to flag when finding mines is the goal
+        if self.is_paused() || self.rules.mine_hunt {
             return;
-        } else {
-            self.map.get_mut(curr_pos).unwrap().state = SquareState::Flagged;
-            self.num_flagged += 1;
+        }
+
+        let square = self.map[curr_pos];
+        match square.state {
+            // exploded mines stay flagged for the rest of the game
+            SquareState::Flagged if !square.exploded => {
+                self.map.get_mut(curr_pos).unwrap().state = SquareState::Covered;
+                self.num_flagged -= 1;
+            }
+            SquareState::Covered if self.num_flagged < self.num_mines => {
+                self.map.get_mut(curr_pos).unwrap().state = SquareState::Flagged;
+                self.num_flagged += 1;
+            }
+            _ => (),
         }
     }
 
     fn find_reveals(&self, curr_pos: &Position) -> HashSet<Position> {
         let curr_square = &self.map[curr_pos];
         if curr_square.is_mine || curr_square.adjacent_mines > 0 {
-            return hashset!{curr_pos.clone()};
+            return hashset!{*curr_pos};
         }
 
         let mut all_reveal: HashSet<Position> = HashSet::new();
         let mut candidates: VecDeque<Position> = VecDeque::new();
-        candidates.push_back(curr_pos.clone());
+        candidates.push_back(*curr_pos);
         let mut visited: HashSet<Position> = HashSet::new();
 
         while !candidates.is_empty() {
@@ -295,9 +554,10 @@ impl MineSweeper {
         assert!(curr_pos.0 < self.rows);
         assert!(curr_pos.1 < self.cols);
 
-        if self.map[curr_pos].state == SquareState::Covered {
-            // frustration remover, if first square is mine, move the mine somewhere else
-            if self.first_move && self.map[curr_pos].is_mine {
+        if self.map[curr_pos].state == SquareState::Covered && !self.is_paused() {
+            // frustration remover, if first square is mine, move the mine somewhere else, in a
+            // mine hunt finding one is the point
+            if self.first_move && self.map[curr_pos].is_mine && !self.rules.mine_hunt {
                 let index = self
                     .mines_index
                     .iter()
@@ -309,11 +569,55 @@ impl MineSweeper {
                 self.populate_board();
             }
 
-            let all_reveal = self.find_reveals(curr_pos);
+            self.reveal_from(curr_pos);
+        }
+    }
 
-            for pos in &all_reveal {
-                self.map.get_mut(pos).unwrap().state = SquareState::Revealed;
+    fn reveal_from(&mut self, curr_pos: &Position) {
+        let square = self.map[curr_pos];
+        // revealing a zero floods a whole opening, which earns countdown bonus time, a zero next to
+        // one already revealed is part of an opening paid for
+        let opened = MineSweeper::get_neighbor_coords(curr_pos, self.cols, self.rows)
+            .iter()
+            .any(|pos| {
+                let neighbor = self.map[pos];
+                neighbor.state == SquareState::Revealed
+                    && !neighbor.is_mine
+                    && neighbor.adjacent_mines == 0
+            });
+        if !square.is_mine && square.adjacent_mines == 0 && !opened {
+            self.bonus += self.rules.bonus_seconds;
+        }
+
+        let all_reveal = self.find_reveals(curr_pos);
+        self.reveal_all(&all_reveal);
+
+        // finding a mine earns another go, anything else hands the turn over
+        if self.rules.mine_hunt && !square.is_mine {
+            self.turn = 1 - self.turn;
+        }
+    }
+
+    fn reveal_all(&mut self, positions: &HashSet<Position>) {
+        for pos in positions {
+            let square = self.map.get_mut(pos).unwrap();
+
+            if square.is_mine && self.rules.mine_hunt {
+                square.claimed_by = Some(self.turn);
+                self.scores[self.turn] += 1;
+            } else if square.is_mine {
+                square.exploded = true;
+                self.explosions += 1;
+
+                // a remaining life absorbs the hit, the mine gets flagged and play continues
+                if self.explosions < self.rules.lives {
+                    square.state = SquareState::Flagged;
+                    self.num_flagged += 1;
+                    continue;
+                }
             }
+
+            square.state = SquareState::Revealed;
         }
     }
 
@@ -321,8 +625,8 @@ impl MineSweeper {
         assert!(curr_pos.0 < self.rows);
         assert!(curr_pos.1 < self.cols);
 
-        let square = self.map[curr_pos].clone();
-        if square.state == SquareState::Revealed {
+        let square = self.map[curr_pos];
+        if square.state == SquareState::Revealed && !self.is_paused() && !self.rules.mine_hunt {
             let neighbors = MineSweeper::get_neighbor_coords(curr_pos, self.cols, self.rows);
             let guesses: HashSet<_> = neighbors
                 .iter()
@@ -337,10 +641,9 @@ impl MineSweeper {
 
             if square.adjacent_mines as usize == guesses.len() {
                 for pos in unknown {
-                    let all_reveal = self.find_reveals(pos);
-
-                    for pos in &all_reveal {
-                        self.map.get_mut(pos).unwrap().state = SquareState::Revealed;
+                    // an earlier opening in this chord may have already uncovered it
+                    if self.map[pos].state == SquareState::Covered {
+                        self.reveal_from(pos);
                     }
                 }
             }
@@ -351,7 +654,206 @@ impl MineSweeper {
         &self.map[&Position(row, col)]
     }
 
+    pub fn get_visible_square(&self, row: u32, col: u32) -> VisibleSquare {
+        let square = self.get_square(row, col);
+        match square.state {
+            SquareState::Covered => VisibleSquare::Covered,
+            _ if square.exploded => VisibleSquare::Exploded,
+            SquareState::Flagged => VisibleSquare::Flagged,
+            SquareState::Revealed if square.is_mine => VisibleSquare::Mine,
+            SquareState::Revealed => VisibleSquare::Revealed(square.adjacent_mines),
+        }
+    }
+
+    // convention [row][col]
+    pub fn get_visible_board(&self) -> Vec<Vec<VisibleSquare>> {
+        (0..self.rows)
+            .map(|i| {
+                (0..self.cols)
+                    .map(|j| self.get_visible_square(i, j))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // whether a move at the position would be accepted, moves themselves assert on bad input
+    pub fn check_move(&self, curr_pos: &Position) -> Result<(), MoveError> {
+        if curr_pos.0 >= self.rows || curr_pos.1 >= self.cols {
+            Err(MoveError::OutOfBounds)
+        } else if self.state != GameState::Ongoing {
+            Err(MoveError::GameOver)
+        } else if self.is_paused() {
+            Err(MoveError::Paused)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Bechtel's Board Benchmark Value, the fewest clicks that clear the board without flagging,
+    // the layout only settles after the first move
+    pub fn get_3bv(&self) -> u32 {
+        let mut visited: HashSet<Position> = HashSet::new();
+        let mut clicks = 0;
+
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                let pos = Position(i, j);
+                let square = &self.map[&pos];
+                if square.is_mine || square.adjacent_mines > 0 || visited.contains(&pos) {
+                    continue;
+                }
+
+                // every opening takes a single click
+                clicks += 1;
+                let mut candidates: VecDeque<Position> = VecDeque::new();
+                candidates.push_back(pos);
+                visited.insert(pos);
+
+                while let Some(curr) = candidates.pop_front() {
+                    if self.map[&curr].adjacent_mines > 0 {
+                        continue;
+                    }
+
+                    for p in MineSweeper::get_neighbor_coords(&curr, self.cols, self.rows) {
+                        if visited.insert(p) {
+                            candidates.push_back(p);
+                        }
+                    }
+                }
+            }
+        }
+
+        // numbers not bordering an opening need their own click
+        let isolated = self
+            .map
+            .iter()
+            .filter(|&(pos, x)| !x.is_mine && !visited.contains(pos))
+            .count() as u32;
+
+        clicks + isolated
+    }
+
     pub fn get_flags_left(&self) -> u32 {
-        self.num_mines - self.num_flagged
+        // auto flagged mines in lives mode can push the count past the mine total
+        self.num_mines.saturating_sub(self.num_flagged)
+    }
+
+    pub fn get_explosions(&self) -> u32 {
+        self.explosions
+    }
+
+    // share of the safe squares uncovered so far, in percent
+    pub fn get_progress(&self) -> u32 {
+        let safe = self.rows * self.cols - self.num_mines;
+        let revealed = self
+            .map
+            .values()
+            .filter(|x| !x.is_mine && x.state == SquareState::Revealed)
+            .count() as u32;
+
+        (revealed * 100).checked_div(safe).unwrap_or(100)
+    }
+
+    // whose turn it is in mine hunt, 0 for the first player
+    pub fn get_turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn get_scores(&self) -> [u32; 2] {
+        self.scores
+    }
+
+    // the mine hunt player with the most mines once the game is over, None on a tie
+    pub fn get_winner(&self) -> Option<usize> {
+        if !self.rules.mine_hunt || self.state == GameState::Ongoing {
+            None
+        } else if self.scores[0] > self.scores[1] {
+            Some(0)
+        } else if self.scores[1] > self.scores[0] {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_lives_left(&self) -> u32 {
+        self.rules.lives.saturating_sub(self.explosions)
+    }
+
+    // remembers the board as it is before a move, for undo to go back to
+    pub fn save_undo(&mut self) {
+        self.undo_stack.push(Snapshot {
+            map: self.map.clone(),
+            mines_index: self.mines_index.clone(),
+            num_flagged: self.num_flagged,
+            explosions: self.explosions,
+            bonus: self.bonus,
+            scores: self.scores,
+            turn: self.turn,
+            first_move: self.first_move,
+            state: self.state,
+        });
+    }
+
+    // forgets the last save when the move after it was turned down, so an undo always goes back to
+    // a board that looks different
+    pub fn drop_undo_if_unchanged(&mut self) {
+        let unchanged = self
+            .undo_stack
+            .last()
+            .is_some_and(|x| x.map == self.map && x.state == self.state);
+        if unchanged {
+            self.undo_stack.pop();
+        }
+    }
+
+    // back to the board as it was at the last save, even out of a lost game,
+    // returns false when there is nothing to go back to
+    pub fn undo(&mut self) -> bool {
+        if self.is_paused() {
+            return false;
+        }
+
+        let snapshot = match self.undo_stack.pop() {
+            Some(snapshot) => snapshot,
+            None => return false,
+        };
+
+        self.map = snapshot.map;
+        self.mines_index = snapshot.mines_index;
+        self.num_flagged = snapshot.num_flagged;
+        self.explosions = snapshot.explosions;
+        self.bonus = snapshot.bonus;
+        self.scores = snapshot.scores;
+        self.turn = snapshot.turn;
+        self.first_move = snapshot.first_move;
+        self.state = snapshot.state;
+        self.undos += 1;
+
+        // back before the first move the clock has not started, the next first move starts it anew
+        if self.first_move {
+            self.elapsed = 0;
+            self.paused_ms = 0;
+            self.pauses.clear();
+        }
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    // times undo was used this game, such games do not count for high scores
+    pub fn get_undo_count(&self) -> u32 {
+        self.undos
+    }
+
+    pub fn add_hint(&mut self) {
+        self.hints += 1;
+    }
+
+    // hints shown this game, such wins are marked in the high scores
+    pub fn get_hint_count(&self) -> u32 {
+        self.hints
     }
 }
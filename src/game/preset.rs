@@ -0,0 +1,104 @@
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Preset {
+    pub name: String,
+    pub cols: u32,
+    pub rows: u32,
+    pub mines: u32,
+}
+
+impl Preset {
+    pub fn new(name: &str, cols: u32, rows: u32, mines: u32) -> Preset {
+        Preset {
+            name: name.to_owned(),
+            cols,
+            rows,
+            mines,
+        }
+    }
+}
+
+// the boards a player can pick from, in the order they are cycled through and numbered
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PresetRegistry {
+    presets: Vec<Preset>,
+}
+
+impl Default for PresetRegistry {
+    fn default() -> PresetRegistry {
+        PresetRegistry::classic()
+    }
+}
+
+impl PresetRegistry {
+    pub fn new() -> PresetRegistry {
+        PresetRegistry {
+            presets: Vec::new(),
+        }
+    }
+
+    // the boards of Windows Minesweeper
+    pub fn classic() -> PresetRegistry {
+        let mut registry = PresetRegistry::new();
+        registry.register(Preset::new("Beginner", 9, 9, 10));
+        registry.register(Preset::new("Intermediate", 16, 16, 40));
+        registry.register(Preset::new("Expert", 30, 16, 99));
+        registry
+    }
+
+    // square boards as this game first shipped them
+    pub fn modern() -> PresetRegistry {
+        let mut registry = PresetRegistry::new();
+        registry.register(Preset::new("Beginner", 8, 8, 10));
+        registry.register(Preset::new("Intermediate", 16, 16, 40));
+        registry.register(Preset::new("Expert", 24, 24, 99));
+        registry
+    }
+
+    pub fn from_set_name(name: &str) -> Option<PresetRegistry> {
+        match &name.to_lowercase()[..] {
+            "classic" => Some(PresetRegistry::classic()),
+            "modern" => Some(PresetRegistry::modern()),
+            _ => None,
+        }
+    }
+
+    // adds the preset at the end, or replaces the one with the same name in place
+    pub fn register(&mut self, preset: Preset) {
+        match self.position(&preset.name) {
+            Some(index) => self.presets[index] = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Preset> {
+        self.presets.get(index)
+    }
+
+    pub fn presets(&self) -> &[Preset] {
+        &self.presets
+    }
+
+    // names match regardless of case
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.presets
+            .iter()
+            .position(|x| x.name.eq_ignore_ascii_case(name))
+    }
+
+    // wraps around from the last preset to the first
+    pub fn get_next(&self, index: usize) -> usize {
+        (index + 1) % self.len().max(1)
+    }
+
+    pub fn get_previous(&self, index: usize) -> usize {
+        (index + self.len().max(1) - 1) % self.len().max(1)
+    }
+}
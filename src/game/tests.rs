@@ -1,4 +1,42 @@
+use super::clock::ManualClock;
 use super::*;
+use super::layout::LayoutError;
+use super::preset::{Preset, PresetRegistry};
+
+// a board with the mines where the test puts them, a first reveal on one moves it to the first
+// safe square
+fn from_mines(cols: u32, rows: u32, mines_index: Vec<usize>) -> MineSweeper {
+    let start_index = (0..).find(|x| !mines_index.contains(x)).unwrap() as u32;
+    let mut game = MineSweeper {
+        cols,
+        rows,
+        num_mines: mines_index.len() as u32,
+        rules: Rules::default(),
+        num_flagged: 0,
+        explosions: 0,
+        seed: 0,
+        mines_index,
+        map: HashMap::new(),
+        first_move: true,
+        clock: Box::new(MonotonicClock::new()),
+        started_at: 0,
+        elapsed: 0,
+        paused_at: None,
+        paused_ms: 0,
+        pauses: Vec::new(),
+        bonus: 0,
+        scores: [0, 0],
+        turn: 0,
+        undo_stack: Vec::new(),
+        undos: 0,
+        hints: 0,
+        start_index,
+        state: GameState::Ongoing,
+    };
+
+    game.populate_board();
+    game
+}
 
 #[test]
 fn test_check_game_won() {
@@ -58,8 +96,8 @@ fn test_update_game_state() {
     // due to first move behavior where mine is moved, need to reveal start position
     let start_index = game.start_index;
     game.reveal_square(&Position(
-        start_index as u32 / rows,
-        start_index as u32 % cols,
+        start_index / rows,
+        start_index % cols,
     ));
 
     game.update_game_state();
@@ -82,7 +120,7 @@ fn test_first_move() {
     assert!(!game.map[&start_pos].is_mine);
 
     let start_index = game.start_index;
-    assert!(game.map[&Position(start_index as u32 / rows, start_index as u32 % cols,)].is_mine)
+    assert!(game.map[&Position(start_index / rows, start_index % cols,)].is_mine)
 }
 
 #[test]
@@ -195,22 +233,7 @@ fn test_get_neighbors() {
 
 #[test]
 fn test_adjacent_mines_num() {
-    let mut game = MineSweeper {
-        cols: 3,
-        rows: 4,
-        num_mines: 3,
-        num_flagged: 0,
-        rng: thread_rng(),
-        mines_index: vec![0, 4, 8],
-        map: HashMap::new(),
-        first_move: true,
-        timer: SystemTime::now(),
-        elapsed: 0,
-        start_index: 1,
-        state: GameState::Ongoing,
-    };
-
-    game.populate_board();
+    let mut game = from_mines(3, 4, vec![0, 4, 8]);
 
     assert_eq!(game.map[&Position(0, 1)].adjacent_mines, 2);
     assert_eq!(game.map[&Position(0, 2)].adjacent_mines, 1);
@@ -265,22 +288,7 @@ fn test_toggle_flag_max() {
 
 #[test]
 fn test_reveal_square() {
-    let mut game = MineSweeper {
-        cols: 3,
-        rows: 3,
-        num_mines: 3,
-        num_flagged: 0,
-        rng: thread_rng(),
-        mines_index: vec![0, 1, 5],
-        map: HashMap::new(),
-        first_move: true,
-        timer: SystemTime::now(),
-        elapsed: 0,
-        start_index: 2,
-        state: GameState::Ongoing,
-    };
-
-    game.populate_board();
+    let mut game = from_mines(3, 3, vec![0, 1, 5]);
 
     game.reveal_square(&Position(2, 0));
 
@@ -306,3 +314,454 @@ fn test_reset() {
 
     assert_eq!(game.get_square(5, 5).state, SquareState::Covered);
 }
+
+#[test]
+fn test_seed() {
+    let mut first = MineSweeper::with_seed(16, 16, 40, Rules::default(), 1234);
+    let mut second = MineSweeper::with_seed(16, 16, 40, Rules::default(), 1234);
+
+    assert_eq!(first.mines_index, second.mines_index);
+    assert_eq!(first.start_index, second.start_index);
+
+    // pinned, so a board shared by seed is laid out the same on every platform
+    let board = MineSweeper::with_seed(5, 5, 4, Rules::default(), 1234);
+    assert_eq!(board.mines_index, vec![23, 11, 16, 6]);
+    assert_eq!(board.start_index, 24);
+
+    // the first click moves mines the same way on both boards
+    let pos = Position(first.mines_index[0] as u32 / 16, first.mines_index[0] as u32 % 16);
+    first.reveal_square(&pos);
+    second.reveal_square(&pos);
+    assert_eq!(first.get_visible_board(), second.get_visible_board());
+    assert_eq!(first.get_progress(), second.get_progress());
+    assert!(!first.get_square(pos.0, pos.1).is_mine);
+
+    second.reset_with_seed(1234);
+    first.reset_with_seed(1234);
+    assert_eq!(first.get_seed(), 1234);
+    assert_eq!(first.mines_index, second.mines_index);
+    assert_eq!(first.get_progress(), 0);
+}
+
+#[test]
+fn test_lives() {
+    let mut game = MineSweeper::with_rules(9, 9, 10, Rules {
+        lives: 2,
+        ..Rules::default()
+    });
+    game.first_moved();
+
+    let mine_index = game.mines_index[0] as u32;
+    let mine_pos = Position(mine_index / game.cols, mine_index % game.cols);
+    game.reveal_square(&mine_pos);
+    game.update_game_state();
+
+    assert_eq!(game.state, GameState::Ongoing);
+    assert_eq!(game.get_lives_left(), 1);
+    assert!(game.map[&mine_pos].exploded);
+    assert_eq!(game.map[&mine_pos].state, SquareState::Flagged);
+    assert_eq!(game.get_flags_left(), 9);
+
+    // exploded mines can not be unflagged
+    game.toggle_flag_square(&mine_pos);
+    assert_eq!(game.map[&mine_pos].state, SquareState::Flagged);
+
+    let mine_index = game.mines_index[1] as u32;
+    game.reveal_square(&Position(mine_index / game.cols, mine_index % game.cols));
+    game.update_game_state();
+
+    assert_eq!(game.state, GameState::Lost);
+    assert_eq!(game.get_lives_left(), 0);
+
+    game.reset();
+
+    assert_eq!(game.get_lives_left(), 2);
+}
+
+#[test]
+fn test_mine_hunt() {
+    let mut game = from_mines(3, 3, vec![0, 1, 5]);
+    game.rules = Rules {
+        mine_hunt: true,
+        ..Rules::default()
+    };
+    game.first_moved();
+
+    // flags have no use when the mines are the prize
+    game.toggle_flag_square(&Position(0, 0));
+    assert_eq!(game.map[&Position(0, 0)].state, SquareState::Covered);
+
+    // a safe square passes the turn
+    game.reveal_square(&Position(2, 0));
+    game.update_game_state();
+    assert_eq!(game.get_turn(), 1);
+    assert_eq!(game.get_scores(), [0, 0]);
+
+    // a mine scores and the same player goes again
+    game.reveal_square(&Position(0, 0));
+    game.update_game_state();
+    assert_eq!(game.state, GameState::Ongoing);
+    assert_eq!(game.get_turn(), 1);
+    assert_eq!(game.get_scores(), [0, 1]);
+    assert_eq!(game.map[&Position(0, 0)].claimed_by, Some(1));
+    assert_eq!(game.get_winner(), None);
+
+    // two of three mines is a majority
+    game.reveal_square(&Position(0, 1));
+    game.update_game_state();
+    assert_eq!(game.state, GameState::Won);
+    assert_eq!(game.get_scores(), [0, 2]);
+    assert_eq!(game.get_winner(), Some(1));
+    assert_eq!(game.lose_reason(), None);
+}
+
+#[test]
+fn test_mine_hunt_first_move() {
+    let mut game = from_mines(3, 3, vec![0, 1, 5]);
+    game.rules = Rules {
+        mine_hunt: true,
+        ..Rules::default()
+    };
+
+    // the opening click scores like any other instead of moving the mine away
+    game.reveal_square(&Position(0, 0));
+    game.first_moved();
+    game.update_game_state();
+    assert_eq!(game.mines_index, vec![0, 1, 5]);
+    assert_eq!(game.map[&Position(0, 0)].claimed_by, Some(0));
+    assert_eq!(game.get_scores(), [1, 0]);
+    assert_eq!(game.get_turn(), 0);
+}
+
+#[test]
+fn test_time_limit() {
+    // a wall of mines down the middle keeps the left opening from finishing the board
+    let mut game = from_mines(5, 3, vec![2, 7, 12]);
+    game.rules = Rules {
+        time_limit: Some(30),
+        bonus_seconds: 5,
+        ..Rules::default()
+    };
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+
+    assert_eq!(game.get_time_left(), Some(30));
+    assert!(!game.is_started());
+
+    game.first_moved();
+    assert!(game.is_started());
+    clock.advance(29_500);
+    game.update_game_state();
+
+    assert_eq!(game.state, GameState::Ongoing);
+    assert_eq!(game.get_time_left_ms(), Some(500));
+
+    game.reveal_square(&Position(0, 0));
+    assert_eq!(game.get_time_left_ms(), Some(5_500));
+    clock.advance(500);
+    game.update_game_state();
+
+    assert_eq!(game.state, GameState::Ongoing);
+
+    clock.advance(5_000);
+    game.update_game_state();
+
+    assert_eq!(game.state, GameState::Lost);
+    assert_eq!(game.lose_reason(), Some(LoseReason::TimeUp));
+    assert_eq!(game.get_time_left(), Some(0));
+}
+
+#[test]
+fn test_game_time() {
+    let mut game = MineSweeper::new(9, 9, 10);
+    let clock = ManualClock::new();
+    clock.set(1_000);
+    game.set_clock(Box::new(clock.clone()));
+
+    // the timer does not run before the first move
+    clock.advance(5_000);
+    assert_eq!(game.elapsed_ms(), 0);
+
+    game.first_moved();
+    clock.advance(1_234);
+    assert_eq!(game.elapsed_ms(), 1_234);
+    assert_eq!(game.game_time(), 1);
+    assert_eq!(game.final_time_ms(), None);
+
+    // time is frozen once the game ends
+    game.state = GameState::Won;
+    clock.advance(10_000);
+    assert_eq!(game.elapsed_ms(), 1_234);
+    assert_eq!(game.final_time_ms(), Some(1_234));
+
+    game.reset();
+    assert_eq!(game.elapsed_ms(), 0);
+}
+
+#[test]
+fn test_opening_bonus() {
+    let rules = Rules {
+        time_limit: Some(30),
+        bonus_seconds: 5,
+        ..Rules::default()
+    };
+    let mut game = from_mines(3, 3, vec![8]);
+    game.rules = rules;
+    game.first_moved();
+
+    // a numbered square is not an opening
+    game.reveal_square(&Position(1, 1));
+    assert_eq!(game.bonus, 0);
+
+    // a zero left flagged inside the opening is still part of it
+    game.toggle_flag_square(&Position(0, 0));
+    game.reveal_square(&Position(0, 2));
+    assert_eq!(game.bonus, 5);
+    assert_eq!(game.get_time_left(), Some(35));
+
+    game.toggle_flag_square(&Position(0, 0));
+    game.reveal_square(&Position(0, 0));
+    assert_eq!(game.get_square(0, 0).state, SquareState::Revealed);
+    assert_eq!(game.bonus, 5);
+}
+
+#[test]
+#[should_panic]
+fn test_time_limit_too_long() {
+    let rules = Rules {
+        time_limit: Some(MAX_TIME + 1),
+        ..Rules::default()
+    };
+    MineSweeper::with_rules(9, 9, 10, rules);
+}
+
+#[test]
+fn test_pause() {
+    let mut game = MineSweeper::new(9, 9, 10);
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+
+    game.first_moved();
+    clock.advance(2_000);
+    game.pause();
+    clock.advance(60_000);
+
+    assert!(game.is_paused());
+    assert_eq!(game.elapsed_ms(), 2_000);
+
+    // moves are ignored while the board is hidden
+    game.toggle_flag_square(&Position(0, 0));
+    assert_eq!(game.get_square(0, 0).state, SquareState::Covered);
+
+    game.resume();
+    clock.advance(500);
+
+    assert!(!game.is_paused());
+    assert_eq!(game.elapsed_ms(), 2_500);
+    assert_eq!(
+        game.pause_intervals(),
+        &[PauseInterval {
+            at_ms: 2_000,
+            duration_ms: 60_000,
+        }]
+    );
+
+    game.reset();
+    assert!(game.pause_intervals().is_empty());
+}
+
+#[test]
+fn test_3bv() {
+    let mut game = from_mines(5, 3, vec![2, 14]);
+
+    // ..*..
+    // .....
+    // ....*
+    // the top right corner is an opening of its own, cut off by numbers
+    assert_eq!(game.get_3bv(), 2);
+
+    game.mines_index = vec![2, 7, 12];
+    game.map.clear();
+    game.populate_board();
+
+    // ..*..
+    // ..*..
+    // ..*..
+    // two openings, one on either side of the wall
+    assert_eq!(game.get_3bv(), 2);
+
+    game.mines_index = vec![0, 2, 4, 10, 12, 14];
+    game.map.clear();
+    game.populate_board();
+
+    // *.*.*
+    // .....
+    // *.*.*
+    // no openings, every safe square is its own click
+    assert_eq!(game.get_3bv(), 9);
+}
+
+#[test]
+fn test_preset_registry() {
+    let mut presets = PresetRegistry::classic();
+    assert_eq!(presets.get(2), Some(&Preset::new("Expert", 30, 16, 99)));
+    assert_eq!(PresetRegistry::from_set_name("Modern").unwrap().get(2).unwrap().cols, 24);
+    assert!(PresetRegistry::from_set_name("huge").is_none());
+
+    presets.register(Preset::new("Tiny", 5, 5, 3));
+    // the same name replaces the preset without moving it
+    presets.register(Preset::new("beginner", 8, 8, 10));
+    assert_eq!(presets.len(), 4);
+    assert_eq!(presets.position("TINY"), Some(3));
+    assert_eq!(presets.get(0).unwrap().cols, 8);
+
+    assert_eq!(presets.get_next(3), 0);
+    assert_eq!(presets.get_previous(0), 3);
+    assert_eq!(presets.get_previous(2), 1);
+
+    let game = MineSweeper::new_from_preset(presets.get(2).unwrap(), Rules::default());
+    assert_eq!((game.cols, game.rows, game.num_mines), (30, 16, 99));
+}
+
+#[test]
+fn test_undo_first_move() {
+    let mut game = from_mines(5, 3, vec![2, 7, 12]);
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+
+    game.save_undo();
+    game.reveal_square(&Position(0, 0));
+    game.first_moved();
+    clock.advance(3_000);
+    game.pause();
+    clock.advance(10_000);
+    game.resume();
+    clock.advance(500);
+    assert_eq!(game.elapsed_ms(), 3_500);
+    assert_eq!(game.pause_intervals().len(), 1);
+
+    assert!(game.undo());
+    assert!(!game.is_started());
+    assert_eq!(game.elapsed_ms(), 0);
+    assert!(game.pause_intervals().is_empty());
+
+    // the next game's clock starts with its own first move
+    clock.advance(7_000);
+    game.save_undo();
+    game.reveal_square(&Position(0, 0));
+    game.first_moved();
+    clock.advance(1_250);
+    assert_eq!(game.elapsed_ms(), 1_250);
+    assert!(game.pause_intervals().is_empty());
+}
+
+#[test]
+fn test_undo() {
+    let mut game = MineSweeper::with_seed(9, 9, 10, Rules::default(), 99);
+    assert!(!game.undo());
+
+    let start = game.get_visible_board();
+    let safe = (0..81)
+        .find(|x| !game.mines_index.contains(x))
+        .unwrap() as u32;
+    game.save_undo();
+    game.reveal_square(&Position(safe / 9, safe % 9));
+    game.first_moved();
+    game.update_game_state();
+
+    // a move the board turns down saves nothing to go back to
+    game.save_undo();
+    game.toggle_flag_square(&Position(safe / 9, safe % 9));
+    game.drop_undo_if_unchanged();
+
+    let after_reveal = game.get_visible_board();
+    let mine = game.mines_index[0] as u32;
+    game.save_undo();
+    game.reveal_square(&Position(mine / 9, mine % 9));
+    game.update_game_state();
+    assert_eq!(game.state, GameState::Lost);
+
+    // back out of the lost game to where it stood, then to the untouched board
+    assert!(game.can_undo());
+    assert!(game.undo());
+    assert_eq!(game.state, GameState::Ongoing);
+    assert_eq!(game.get_visible_board(), after_reveal);
+    assert_eq!(game.get_explosions(), 0);
+    assert!(game.undo());
+    assert_eq!(game.get_visible_board(), start);
+    assert!(!game.can_undo());
+    assert_eq!(game.get_undo_count(), 2);
+
+    game.reset();
+    assert_eq!(game.get_undo_count(), 0);
+}
+
+#[test]
+fn test_layout() {
+    let layout = "##*#\nF2##\n.1M#";
+    let game = MineSweeper::from_layout(layout, Rules::default()).unwrap();
+
+    assert_eq!((game.cols, game.rows, game.num_mines), (4, 3, 2));
+    assert_eq!(game.get_flags_left(), 0);
+    assert_eq!(game.state, GameState::Ongoing);
+    assert!(game.get_square(0, 2).is_mine);
+    assert_eq!(game.get_square(1, 0).state, SquareState::Flagged);
+    assert_eq!(game.get_square(2, 0).state, SquareState::Revealed);
+    assert_eq!(game.get_square(1, 2).adjacent_mines, 2);
+    assert_eq!(game.get_layout(), layout);
+
+    // blank lines and indentation are left out
+    let game = MineSweeper::from_layout("\n  X1\n  11\n", Rules::default()).unwrap();
+    assert_eq!(game.state, GameState::Lost);
+    assert_eq!(game.get_visible_square(0, 0), VisibleSquare::Exploded);
+    assert_eq!(game.get_layout(), "X1\n11");
+
+    let lives = Rules {
+        lives: 2,
+        ..Rules::default()
+    };
+    let game = MineSweeper::from_layout("E1\n11", lives).unwrap();
+    assert_eq!(game.state, GameState::Won);
+    assert_eq!(game.get_lives_left(), 1);
+
+    assert_eq!(
+        MineSweeper::from_layout("#", Rules::default()).err(),
+        Some(LayoutError::TooSmall)
+    );
+    assert_eq!(
+        MineSweeper::from_layout("##\n#", Rules::default()).err(),
+        Some(LayoutError::Ragged(1))
+    );
+    assert_eq!(
+        MineSweeper::from_layout("#?", Rules::default()).err(),
+        Some(LayoutError::UnknownSquare('?', Position(0, 1)))
+    );
+    assert_eq!(
+        MineSweeper::from_layout("2*", Rules::default()).err(),
+        Some(LayoutError::WrongNumber(Position(0, 0)))
+    );
+}
+
+#[test]
+fn test_mask() {
+    let mask = [
+        true, true, false, false,
+        true, true, false, false,
+        false, false, false, false,
+    ];
+    let mut game = MineSweeper::with_mask(4, 3, vec![2, 10], &mask, Rules::default());
+
+    assert_eq!(game.get_layout(), ".1*#\n.2##\n##*#");
+    assert_eq!(game.state, GameState::Ongoing);
+    assert!(game.is_started());
+    assert_eq!(game.num_mines, 2);
+
+    // a mine is not moved out of the way once the puzzle has started
+    game.reveal_square(&Position(2, 2));
+    game.update_game_state();
+    assert_eq!(game.state, GameState::Lost);
+
+    let game = MineSweeper::with_mask(2, 1, vec![1], &[false, false], Rules::default());
+    assert!(!game.is_started());
+    assert_eq!(game.get_layout(), "#*");
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, BufRead, Write};
+
+use game::{GameState, MineSweeper, Position, VisibleSquare};
+
+// line based protocol for bots, every command line gets exactly one response
+//
+//   reveal <row> <col>   -> ok <status> | error <reason>
+//   flag <row> <col>     -> ok <status> | error <reason>
+//   chord <row> <col>    -> ok <status> | error <reason>
+//   reset                -> ok <status>
+//   state                -> state <status> <rows> <cols> <flags left> <time ms>
+//                           followed by one line of squares per row
+//   quit                 -> ends the session
+pub struct Session {
+    game: MineSweeper,
+}
+
+pub fn get_square_char(square: VisibleSquare) -> char {
+    match square {
+        VisibleSquare::Covered => '#',
+        VisibleSquare::Flagged => 'F',
+        VisibleSquare::Revealed(0) => '.',
+        VisibleSquare::Revealed(n) => ::std::char::from_digit(n, 10).unwrap(),
+        VisibleSquare::Mine => '*',
+        VisibleSquare::Exploded => 'X',
+    }
+}
+
+pub fn get_board_text(game: &MineSweeper) -> String {
+    game.get_visible_board()
+        .iter()
+        .map(|row| row.iter().map(|x| get_square_char(*x)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn get_status(game: &MineSweeper) -> &'static str {
+    match game.state {
+        GameState::Ongoing => "ongoing",
+        GameState::Won => "won",
+        GameState::Lost => "lost",
+    }
+}
+
+impl Session {
+    pub fn new(game: MineSweeper) -> Session {
+        Session { game }
+    }
+
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+
+            if line.trim() == "quit" {
+                break;
+            }
+
+            writeln!(output, "{}", self.handle_command(&line))?;
+            output.flush()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn handle_command(&mut self, line: &str) -> String {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let args: Vec<_> = words.collect();
+
+        match command {
+            "reveal" | "flag" | "chord" => match Session::parse_position(&args) {
+                Some(pos) => self.handle_move(command, &pos),
+                None => format!("error usage: {} <row> <col>", command),
+            },
+            "reset" => {
+                self.game.reset();
+                format!("ok {}", get_status(&self.game))
+            }
+            "state" => {
+                // a countdown can run out between moves
+                self.game.update_game_state();
+                let time_ms = self.game.elapsed_ms();
+                format!(
+                    "state {} {} {} {} {}\n{}",
+                    get_status(&self.game),
+                    self.game.rows,
+                    self.game.cols,
+                    self.game.get_flags_left(),
+                    time_ms,
+                    get_board_text(&self.game)
+                )
+            }
+            _ => format!("error unknown command: {}", command),
+        }
+    }
+
+    fn parse_position(args: &[&str]) -> Option<Position> {
+        if args.len() != 2 {
+            return None;
+        }
+
+        match (args[0].parse::<u32>(), args[1].parse::<u32>()) {
+            (Ok(row), Ok(col)) => Some(Position(row, col)),
+            _ => None,
+        }
+    }
+
+    fn handle_move(&mut self, command: &str, curr_pos: &Position) -> String {
+        if let Err(err) = self.game.check_move(curr_pos) {
+            return format!("error {}", err);
+        }
+
+        match command {
+            "reveal" => {
+                self.game.reveal_square(curr_pos);
+                self.game.first_moved();
+            }
+            "flag" => self.game.toggle_flag_square(curr_pos),
+            _ => self.game.try_reveal_adjacent(curr_pos),
+        }
+
+        self.game.update_game_state();
+        format!("ok {}", get_status(&self.game))
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+use game::clock::ManualClock;
+use game::Rules;
+use std::io::Cursor;
+
+#[test]
+fn test_invalid_commands() {
+    let mut session = Session::new(MineSweeper::new(9, 9, 10));
+
+    assert_eq!(
+        session.handle_command("reveal 9 0"),
+        "error position out of bounds"
+    );
+    assert_eq!(
+        session.handle_command("flag 1"),
+        "error usage: flag <row> <col>"
+    );
+    assert_eq!(
+        session.handle_command("chord a b"),
+        "error usage: chord <row> <col>"
+    );
+    assert_eq!(
+        session.handle_command("explode"),
+        "error unknown command: explode"
+    );
+}
+
+#[test]
+fn test_state() {
+    let mut session = Session::new(MineSweeper::new(3, 2, 2));
+
+    assert_eq!(
+        session.handle_command("state"),
+        "state ongoing 2 3 2 0\n###\n###"
+    );
+
+    assert_eq!(session.handle_command("flag 1 2"), "ok ongoing");
+    assert!(session.handle_command("state").ends_with("###\n##F"));
+}
+
+#[test]
+fn test_state_time_up() {
+    let rules = Rules {
+        time_limit: Some(10),
+        ..Rules::default()
+    };
+    let mut game = MineSweeper::with_rules(9, 9, 10, rules);
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+    let mut session = Session::new(game);
+
+    session.game.first_moved();
+    clock.advance(10_000);
+
+    assert!(session.handle_command("state").starts_with("state lost "));
+}
+
+#[test]
+fn test_run() {
+    let mut session = Session::new(MineSweeper::new(9, 9, 10));
+    let input = Cursor::new("flag 0 0\nflag 0 0\nquit\nstate\n");
+    let mut output = Vec::new();
+
+    session.run(input, &mut output).unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), "ok ongoing\nok ongoing\n");
+}
+
+#[test]
+fn test_game_over() {
+    let mut session = Session::new(MineSweeper::new(9, 9, 10));
+
+    assert_eq!(session.handle_command("reveal 4 4"), "ok ongoing");
+
+    // keep revealing until something goes wrong, only then moves are refused
+    for i in 0..9 {
+        for j in 0..9 {
+            session.handle_command(&format!("reveal {} {}", i, j));
+        }
+    }
+
+    assert_eq!(session.game.state, GameState::Lost);
+    assert_eq!(session.handle_command("reveal 0 0"), "error game is over");
+    assert_eq!(session.handle_command("reset"), "ok ongoing");
+}
@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod tests;
+
+use game::{MineSweeper, Position, VisibleSquare};
+use solver::{self, Board, Deduction, Reason};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HintKind {
+    Safe,
+    Mine,
+    // nothing is certain, the square least likely to be a mine
+    Guess,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hint {
+    pub pos: Position,
+    pub kind: HintKind,
+    // the revealed numbers the reasoning rests on
+    pub sources: Vec<Position>,
+    pub explanation: String,
+}
+
+// one square the player can be sure of, safe ones first as they move the game along, or the
+// least risky guess when there is none, None once nothing is left covered
+//
+// the player's flags could be wrong, so they only count once the numbers prove them, a flag on a
+// safe square gets that square hinted as safe
+pub fn find_hint(game: &MineSweeper) -> Option<Hint> {
+    let mut board = game.get_visible_board();
+    for square in board.iter_mut().flat_map(|row| row.iter_mut()) {
+        if *square == VisibleSquare::Flagged {
+            *square = VisibleSquare::Covered;
+        }
+    }
+
+    // the mines move out of the way of the first reveal
+    if !game.is_started() {
+        return Some(Hint {
+            pos: Position(0, 0),
+            kind: HintKind::Safe,
+            sources: Vec::new(),
+            explanation: "The first square revealed is never a mine, and a corner is the \
+                          likeliest to open up more."
+                .to_owned(),
+        });
+    }
+
+    // a mine already flagged is no help, but proves the flag for the next round of deductions
+    loop {
+        let deductions = solver::find_deductions(&board, game.num_mines);
+        let flagged = |pos: &Position| {
+            game.get_visible_square(pos.0, pos.1) == VisibleSquare::Flagged
+        };
+        if let Some(deduction) = deductions
+            .iter()
+            .find(|x| !x.is_mine)
+            .or_else(|| deductions.iter().find(|x| !flagged(&x.pos)))
+        {
+            return Some(get_deduction_hint(&board, deduction));
+        }
+        if deductions.is_empty() {
+            break;
+        }
+
+        for deduction in deductions {
+            board[deduction.pos.0 as usize][deduction.pos.1 as usize] = VisibleSquare::Flagged;
+        }
+    }
+
+    solver::find_safest_guess(&board, game.num_mines).map(|(pos, p)| Hint {
+        pos,
+        kind: HintKind::Guess,
+        sources: get_numbers_around(&board, &pos),
+        explanation: format!(
+            "Nothing is certain, this square has the lowest chance of a mine, {:.0}%.",
+            p * 100.0
+        ),
+    })
+}
+
+fn get_deduction_hint(board: &Board, deduction: &Deduction) -> Hint {
+    let pos = deduction.pos;
+    let is_mine = deduction.is_mine;
+
+    let (sources, explanation) = match deduction.reason {
+        Reason::Single(source) => {
+            let explanation = if is_mine {
+                format!(
+                    "{} still needs {} and has exactly that many covered squares around it.",
+                    capitalize(&describe(board, &source)),
+                    count(get_number(board, &source).mines, "mine", "mines")
+                )
+            } else {
+                format!(
+                    "{} has all its mines accounted for, so its other squares are safe.",
+                    capitalize(&describe(board, &source))
+                )
+            };
+            (vec![source], explanation)
+        }
+        Reason::Pair(a, b) => (vec![a, b], explain_pair(board, &a, &b, is_mine)),
+        Reason::MineCount => {
+            let explanation = if is_mine {
+                "There are as many mines left as covered squares, so every one is a mine."
+            } else {
+                "Every mine is already marked, so all the covered squares left are safe."
+            };
+            (Vec::new(), explanation.to_owned())
+        }
+        Reason::Enumeration => {
+            let sources = get_numbers_around(board, &pos);
+            let around = if sources.is_empty() {
+                "the mines left could lie".to_owned()
+            } else {
+                let numbers: Vec<_> = sources.iter().map(|x| describe(board, x)).collect();
+                format!("the mines could lie around {}", join(&numbers))
+            };
+            let outcome = if is_mine { "every one" } else { "none" };
+            (
+                sources,
+                format!("Of every way {}, {} puts a mine here.", around, outcome),
+            )
+        }
+    };
+
+    Hint {
+        pos,
+        kind: if is_mine { HintKind::Mine } else { HintKind::Safe },
+        sources,
+        explanation,
+    }
+}
+
+// a number's mines still to find and the covered squares they can be in
+struct Number {
+    value: u32,
+    mines: u32,
+    cells: Vec<Position>,
+}
+
+fn get_number(board: &Board, pos: &Position) -> Number {
+    let value = match board[pos.0 as usize][pos.1 as usize] {
+        VisibleSquare::Revealed(value) => value,
+        _ => 0,
+    };
+
+    let neighbors = solver::get_neighbors(board, pos);
+    let known = neighbors
+        .iter()
+        .filter(|x| {
+            matches!(
+                board[x.0 as usize][x.1 as usize],
+                VisibleSquare::Flagged | VisibleSquare::Mine | VisibleSquare::Exploded
+            )
+        })
+        .count() as u32;
+
+    Number {
+        value,
+        mines: value.saturating_sub(known),
+        cells: neighbors
+            .into_iter()
+            .filter(|x| board[x.0 as usize][x.1 as usize] == VisibleSquare::Covered)
+            .collect(),
+    }
+}
+
+// the same bounds the solver works out on the mines where the two numbers touch
+fn explain_pair(board: &Board, a_pos: &Position, b_pos: &Position, is_mine: bool) -> String {
+    let a = get_number(board, a_pos);
+    let b = get_number(board, b_pos);
+    let shared = a.cells.iter().filter(|x| b.cells.contains(x)).count() as u32;
+    let a_only = a.cells.len() as u32 - shared;
+    let b_only = b.cells.len() as u32 - shared;
+
+    let (a_name, b_name) = (describe(board, a_pos), describe(board, b_pos));
+    if is_mine {
+        let max_shared = *[shared, a.mines, b.mines].iter().min().unwrap();
+        let rest = if b_only == 1 {
+            "its other square is a mine".to_owned()
+        } else {
+            format!("its other {} squares are all mines", b_only)
+        };
+        format!(
+            "Where {} touches {} there is room for at most {} of its {}, so {}.",
+            b_name,
+            a_name,
+            max_shared,
+            count(b.mines, "mine", "mines"),
+            rest
+        )
+    } else {
+        let min_shared = a.mines.saturating_sub(a_only);
+        format!(
+            "{} puts at least {} where it touches {}, which is all the {} has left, so its \
+             other squares are safe.",
+            capitalize(&a_name),
+            count(min_shared, "mine", "mines"),
+            b_name,
+            b.value
+        )
+    }
+}
+
+fn get_numbers_around(board: &Board, pos: &Position) -> Vec<Position> {
+    solver::get_neighbors(board, pos)
+        .into_iter()
+        .filter(|x| match board[x.0 as usize][x.1 as usize] {
+            VisibleSquare::Revealed(value) => value > 0,
+            _ => false,
+        })
+        .collect()
+}
+
+// rows and columns counted from 1 at the top left
+fn describe(board: &Board, pos: &Position) -> String {
+    format!(
+        "the {} at row {}, col {}",
+        get_number(board, pos).value,
+        pos.0 + 1,
+        pos.1 + 1
+    )
+}
+
+fn count(n: u32, one: &str, many: &str) -> String {
+    format!("{} {}", n, if n == 1 { one } else { many })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn join(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+
+use game::Rules;
+
+fn get_hint(layout: &str) -> Hint {
+    find_hint(&MineSweeper::from_layout(layout, Rules::default()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_first_hint() {
+    let mut game = MineSweeper::with_seed(9, 9, 10, Rules::default(), 7);
+    let hint = find_hint(&game).unwrap();
+
+    assert_eq!((hint.pos, hint.kind), (Position(0, 0), HintKind::Safe));
+    assert!(hint.sources.is_empty());
+
+    game.add_hint();
+    assert_eq!(game.get_hint_count(), 1);
+    game.reset();
+    assert_eq!(game.get_hint_count(), 0);
+}
+
+#[test]
+fn test_single_hint() {
+    // safe squares come before mines
+    assert_eq!(
+        get_hint("M1#\n11#"),
+        Hint {
+            pos: Position(0, 2),
+            kind: HintKind::Safe,
+            sources: vec![Position(0, 1)],
+            explanation: "The 1 at row 1, col 2 has all its mines accounted for, so its other \
+                          squares are safe."
+                .to_owned(),
+        }
+    );
+
+    assert_eq!(
+        get_hint("*1."),
+        Hint {
+            pos: Position(0, 0),
+            kind: HintKind::Mine,
+            sources: vec![Position(0, 1)],
+            explanation: "The 1 at row 1, col 2 still needs 1 mine and has exactly that many \
+                          covered squares around it."
+                .to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_unflagged_single_hint() {
+    // the mine that went off is accounted for without the player flagging anything
+    let lives = Rules {
+        lives: 2,
+        ..Rules::default()
+    };
+    let game = MineSweeper::from_layout("E1#\n11#", lives).unwrap();
+
+    assert_eq!(
+        find_hint(&game).unwrap(),
+        Hint {
+            pos: Position(0, 2),
+            kind: HintKind::Safe,
+            sources: vec![Position(0, 1)],
+            explanation: "The 1 at row 1, col 2 has all its mines accounted for, so its other \
+                          squares are safe."
+                .to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_misplaced_flag_hint() {
+    // the flag is on the safe square, the mine is on the other side of the 1
+    let hint = get_hint("F1*\n.11");
+
+    assert_eq!((hint.pos, hint.kind), (Position(0, 0), HintKind::Safe));
+    assert_eq!(hint.sources, vec![Position(1, 0)]);
+}
+
+#[test]
+fn test_pair_hint() {
+    // the 1-2-1 pattern
+    let hint = get_hint("*#*\n121");
+
+    assert_eq!((hint.pos, hint.kind), (Position(0, 2), HintKind::Mine));
+    assert_eq!(hint.sources, vec![Position(1, 0), Position(1, 1)]);
+    assert_eq!(
+        hint.explanation,
+        "Where the 2 at row 2, col 2 touches the 1 at row 2, col 1 there is room for at most 1 \
+         of its 2 mines, so its other square is a mine."
+    );
+}
+
+#[test]
+fn test_guess_hint() {
+    let hint = get_hint("*1#");
+
+    assert_eq!((hint.pos, hint.kind), (Position(0, 0), HintKind::Guess));
+    assert_eq!(hint.sources, vec![Position(0, 1)]);
+    assert!(hint.explanation.ends_with("50%."));
+
+    let game = MineSweeper::from_layout("@1\n11", Rules::default()).unwrap();
+    assert_eq!(find_hint(&game), None);
+}
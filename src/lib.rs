@@ -0,0 +1,27 @@
+extern crate chrono;
+extern crate dirs;
+extern crate image;
+extern crate rand;
+extern crate rusttype;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate toml;
+#[macro_use]
+extern crate maplit;
+
+pub mod bot;
+pub mod config;
+pub mod coop;
+pub mod daily;
+pub mod game;
+pub mod headless;
+pub mod hint;
+pub mod palette;
+pub mod puzzle;
+pub mod race;
+pub mod render;
+pub mod scores;
+pub mod skin;
+pub mod solver;
+pub mod tutorial;
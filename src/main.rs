@@ -1,17 +1,35 @@
-extern crate piston_window;
-extern crate rand;
-#[macro_use]
-extern crate maplit;
+extern crate chrono;
 extern crate clap;
+#[macro_use]
+extern crate crossterm;
 extern crate find_folder;
+extern crate minesweep_rs;
+extern crate piston_window;
+extern crate rand;
 
+mod bindings;
 mod frontend;
-mod game;
+mod tui;
 
-use clap::App;
-use game::Difficulty;
+use clap::{App, ArgMatches, SubCommand};
+use minesweep_rs::game::preset::{Preset, PresetRegistry};
+use minesweep_rs::game::{MineSweeper, Rules, MAX_TIME};
+use minesweep_rs::bot::{self, Player};
+use minesweep_rs::config::{Config, CustomBoard};
+use minesweep_rs::coop::{CoopBoard, CoopClient, CoopServer};
+use minesweep_rs::daily;
+use minesweep_rs::headless;
+use minesweep_rs::palette;
+use minesweep_rs::puzzle::{self, Rating};
+use minesweep_rs::race::{RaceBoard, RaceClient, RaceServer};
+use minesweep_rs::render::{svg, RenderOptions, Renderer, View};
+use minesweep_rs::skin;
+use minesweep_rs::tutorial;
 use piston_window::*;
 use std::cmp::min;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
 fn main() {
     let app = App::new("minesweep-rs")
@@ -19,104 +37,331 @@ fn main() {
         .author("Eric Fang")
         .about("Clone of Windows Minesweeper written in Rust")
         .args_from_usage(
-            "-d, --difficulty=[LEVEL]  'Preset Difficulty Level, 1=Beginner 2=Intermediate 3=Expert 4=Custom'
+            "-d, --difficulty=[LEVEL]  'Preset Difficulty Level, 1=Beginner 2=Intermediate 3=Expert 4=Custom, 5 and up or a name for config presets'
+                    --presets=[SET]           'Sets the built-in presets, classic (Windows boards) or modern'
+                    -r, --rows=[ROWS]         'Sets number of rows (Custom level only)'
+                    -c, --cols=[COLS]         'Sets number of columns (Custom level only)'
+                    -m, --mines=[MINES]       'Sets max number of mines (Custom level only)'
+                    -l, --lives=[LIVES]       'Sets number of mine hits survived before losing'
+                    -t, --time-limit=[SECONDS] 'Sets a countdown, the game is lost when it reaches zero'
+                    -b, --bonus=[SECONDS]     'Sets seconds gained per opening cleared (time limit only)'
+                    -u, --ui=[UI]             'User interface, gui=window tui=terminal'
+                    --skin=[NAME]             'Sets the skin, one of the directories in assets/skins'
+                    -z, --zoom=[FACTOR]       'Sets the window size as a multiple of the board, 1 by default'
+                    --hunt                    'Two players take turns finding mines, most mines wins'
+                    --daily                   'Plays the day's board for the difficulty, the same for everyone (window only)'
+                    --config=[FILE]           'Sets the config file, config.toml in the user config directory by default'
+                    --headless                'Play through text commands on stdin/stdout, no window'")
+        .subcommand(
+            SubCommand::with_name("tournament")
+                .about("Runs the built-in solver bot on every preset difficulty and reports how it did")
+                .args_from_usage(
+                    "-g, --games=[GAMES]  'Sets number of games per difficulty'
+                    --presets=[SET]      'Sets the built-in presets, classic (Windows boards) or modern'",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("serve")
+                .about("Hosts a race where every player gets the same board, or one shared board")
+                .args_from_usage(
+                    "-p, --port=[PORT]         'Sets port to listen on'
+                    -d, --difficulty=[LEVEL]  'Preset Difficulty Level, 1=Beginner 2=Intermediate 3=Expert 4=Custom, 5 and up or a name for config presets'
+                    --presets=[SET]           'Sets the built-in presets, classic (Windows boards) or modern'
                     -r, --rows=[ROWS]         'Sets number of rows (Custom level only)'
                     -c, --cols=[COLS]         'Sets number of columns (Custom level only)'
-                    -m, --mines=[MINES]       'Sets max number of mines (Custom level only)'");
+                    -m, --mines=[MINES]       'Sets max number of mines (Custom level only)'
+                    -l, --lives=[LIVES]       'Sets number of mine hits survived before losing'
+                    -s, --seed=[SEED]         'Sets the board seed, random if not given'
+                    --coop                    'Hosts one board played together instead of a race'",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("race")
+                .about("Joins a race hosted with the serve subcommand")
+                .args_from_usage(
+                    "<ADDRESS>             'Server address, such as 127.0.0.1:7878'
+                    -n, --name=[NAME]     'Sets the name shown to the other players'",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("coop")
+                .about("Joins a shared board hosted with serve --coop")
+                .args_from_usage("<ADDRESS>  'Server address, such as 127.0.0.1:7878'"),
+        )
+        .subcommand(
+            SubCommand::with_name("puzzle")
+                .about("Plays boards that start partly revealed and never need a guess")
+                .args_from_usage(
+                    "[FILE]                  'Puzzle file, board layouts separated by blank lines, generated for the difficulty if left out'
+                    -r, --rating=[RATING]   'Sets the rating of generated puzzles, easy (default), medium or hard'",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("tutorial")
+                .about("Teaches the rules and the common patterns step by step")
+                .args_from_usage("[DIR]  'Directory of lesson files, assets/lessons if left out'"),
+        )
+        .subcommand(
+            SubCommand::with_name("render")
+                .about("Draws a board to a PNG or SVG image the way the window shows it")
+                .args_from_usage(
+                    "--board=<FILE>          'Board layout file, one line per row, - for stdin'
+                    -o, --out=<FILE>        'Sets the image to write, SVG if it ends in .svg, PNG otherwise'
+                    -s, --scale=[SCALE]     'Sets image pixels per window pixel, 1 by default'
+                    -l, --lives=[LIVES]     'Sets number of mine hits survived before losing'
+                    --time=[MS]             'Sets the time shown in milliseconds, 0 by default'
+                    --palette=[NAME]        'Sets the number colors, Default, Okabe-Ito or Tol'
+                    --high-contrast         'Draws in high contrast'
+                    --patterns              'Marks flags and exploded mines with shapes'
+                    --view=[VIEW]           'Sets what is drawn, board as played, puzzle or solution'
+                    --misflags              'Crosses out flags on safe squares'",
+                ),
+        );
 
     let matches = app.get_matches();
 
-    let difficulty = matches.value_of("difficulty").unwrap_or("1");
+    let mut config_path = matches
+        .value_of("config")
+        .map(PathBuf::from)
+        .or_else(Config::get_default_path);
+    let mut config = Config::default();
 
-    let rows = matches
-        .value_of("rows")
-        .unwrap_or("15")
-        .parse::<u32>()
-        .unwrap();
-    let cols = matches
-        .value_of("cols")
-        .unwrap_or("12")
-        .parse::<u32>()
-        .unwrap();
-    let max_mines = matches
-        .value_of("mines")
-        .unwrap_or("13")
-        .parse::<u32>()
-        .unwrap();
+    // a config that does not load is never written to, so nothing in it is lost
+    if let Some(path) = config_path.take() {
+        match Config::load(&path) {
+            Ok(loaded) => {
+                config = loaded;
+                config_path = Some(path);
+            }
+            Err(err) => eprintln!("ignoring config {}: {}", path.display(), err),
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("tournament") {
+        let games = matches
+            .value_of("games")
+            .unwrap_or("1000")
+            .parse::<u32>()
+            .unwrap();
+        let presets = get_preset_set(matches, &config);
+        run_tournament(&mut bot::SolverBot, &presets, games);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("serve") {
+        serve(matches, &config);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("render") {
+        render_board(matches, &config);
+        return;
+    }
+
+    // command line options win over the config
+    let default_rules = config.rules.get_rules();
+    let lives = get_lives(&matches, default_rules.lives);
 
-    let mut front = match difficulty {
-        "1" => frontend::Gui::new(8, 8, 10, Difficulty::Beginner),
-        "2" => frontend::Gui::new(16, 16, 40, Difficulty::Intermediate),
-        "3" => frontend::Gui::new(24, 24, 99, Difficulty::Expert),
-        "4" => frontend::Gui::new(cols, rows, min(max_mines, rows * cols), Difficulty::Custom),
-        _ => panic!("invalid difficulty level!"),
+    let time_limit = get_time_limit(&matches, default_rules.time_limit);
+    let bonus_seconds = matches
+        .value_of("bonus")
+        .map(|bonus| bonus.parse::<u64>().unwrap())
+        .unwrap_or(default_rules.bonus_seconds);
+
+    let rules = Rules {
+        lives,
+        time_limit,
+        bonus_seconds,
+        mine_hunt: matches.is_present("hunt"),
     };
 
-    let mut window: PistonWindow = WindowSettings::new("Mine Sweeper", front.get_window_size())
-        .resizable(false)
-        .exit_on_esc(true)
-        .build()
-        .unwrap();
+    let presets = get_presets(&matches, &config);
+    let preset_index = get_preset_index(&matches, &presets);
+    let preset = presets.get(preset_index).unwrap().clone();
+
+    // the last custom board played becomes the default for the next run
+    let custom = CustomBoard {
+        cols: preset.cols,
+        rows: preset.rows,
+        mines: preset.mines,
+    };
+    if presets.position("Custom") == Some(preset_index) && config.custom != custom {
+        config.custom = custom;
+
+        if let Some(ref path) = config_path {
+            if let Err(err) = Config::save_custom(path, &custom) {
+                eprintln!("could not save config to {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    if matches.is_present("headless") {
+        let game = MineSweeper::new_from_preset(&preset, rules);
+        let stdin = io::stdin();
+        headless::Session::new(game)
+            .run(stdin.lock(), &mut io::stdout())
+            .unwrap();
+        return;
+    }
+
+    match matches.value_of("ui").unwrap_or("gui") {
+        "gui" => (),
+        "tui" => {
+            let mut front = tui::Tui::new(presets, preset_index, rules);
+            if let Some(index) = config.palette.as_ref().and_then(|x| palette::find_palette(x)) {
+                front.set_palette(palette::PALETTES[index]);
+            }
+            front.run().unwrap();
+            return;
+        }
+        _ => panic!("invalid user interface!"),
+    }
+
+    let mut front = frontend::Gui::new(presets, preset_index, rules);
+    front.apply_config(&config, config_path.clone());
+    front.set_zoom(
+        matches
+            .value_of("zoom")
+            .map(|zoom| zoom.parse::<f64>().unwrap())
+            .or(config.zoom)
+            .unwrap_or(1.0),
+    );
 
     let assets = find_folder::Search::ParentsThenKids(3, 3)
         .for_folder("assets")
         .unwrap();
 
-    let font = &assets.join("Andale-Mono.ttf");
-    let mine = &assets.join("mine.png");
-    let flag = &assets.join("flag.png");
-    let win_face = &assets.join("cool.png");
-    let ongoing_face = &assets.join("happy.png");
-    let lost_face = &assets.join("shocked.png");
-
-    let factory = window.factory.clone();
-
-    let mut glyphs = Glyphs::new(
-        font,
-        factory,
-        TextureSettings::new().filter(Filter::Nearest),
-    ).unwrap();
-
-    let icons = frontend::Icons {
-        mine: Texture::from_path(
-            &mut window.factory,
-            &mine,
-            Flip::None,
-            &TextureSettings::new(),
-        ).unwrap(),
-        flag: Texture::from_path(
-            &mut window.factory,
-            &flag,
-            Flip::None,
-            &TextureSettings::new(),
-        ).unwrap(),
-        win_face: Texture::from_path(
-            &mut window.factory,
-            &win_face,
-            Flip::None,
-            &TextureSettings::new(),
-        ).unwrap(),
-        ongoing_face: Texture::from_path(
-            &mut window.factory,
-            &ongoing_face,
-            Flip::None,
-            &TextureSettings::new(),
-        ).unwrap(),
-        lost_face: Texture::from_path(
-            &mut window.factory,
-            &lost_face,
-            Flip::None,
-            &TextureSettings::new(),
-        ).unwrap(),
-    };
+    if matches.is_present("daily") {
+        front.start_daily(daily::get_default_path());
+    }
+
+    if let Some(matches) = matches.subcommand_matches("puzzle") {
+        // a file or rating that gives no puzzles leaves the normal game to play
+        let source = match matches.value_of("FILE") {
+            Some(path) => match puzzle::load_puzzles(Path::new(path)) {
+                Ok(ref puzzles) if puzzles.is_empty() => {
+                    eprintln!("no puzzles in {}, playing the normal game", path);
+                    None
+                }
+                Ok(puzzles) => Some(frontend::PuzzleSource::File(
+                    puzzles
+                        .iter()
+                        .map(|&(ref game, rating)| (puzzle::get_key(game), rating))
+                        .collect(),
+                )),
+                Err(err) => {
+                    eprintln!("could not load puzzles, playing the normal game: {}", err);
+                    None
+                }
+            },
+            None => {
+                let rating = matches.value_of("rating").unwrap_or("easy");
+                match Rating::parse(rating) {
+                    Some(rating) => Some(frontend::PuzzleSource::Generated(rating)),
+                    None => {
+                        eprintln!("unknown rating {}, playing the normal game", rating);
+                        None
+                    }
+                }
+            }
+        };
+        if let Some(source) = source {
+            front.start_puzzles(source, puzzle::get_default_path());
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("tutorial") {
+        let dir = match matches.value_of("DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => assets.join("lessons"),
+        };
+
+        let mut lessons = Vec::new();
+        for lesson in tutorial::find_lessons(&dir) {
+            match lesson {
+                Ok(lesson) => lessons.push(lesson),
+                Err(err) => eprintln!("ignoring lesson {}", err),
+            }
+        }
+        if lessons.is_empty() {
+            panic!("no lessons found!");
+        }
+        front.start_tutorial(lessons);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("race") {
+        let client = RaceClient::connect(
+            matches.value_of("ADDRESS").unwrap(),
+            matches.value_of("name").unwrap_or("player"),
+        ).unwrap();
+        front.join_race(client);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("coop") {
+        let client = CoopClient::connect(matches.value_of("ADDRESS").unwrap()).unwrap();
+        front.join_coop(client);
+    }
+
+    let mut title = front.get_title();
+    let mut window: PistonWindow = WindowSettings::new(title.clone(), front.get_window_size())
+        .resizable(true)
+        .exit_on_esc(true)
+        .build()
+        .unwrap();
+
+    let mut skins = Vec::new();
+    for skin in skin::find_skins(&assets.join("skins")) {
+        match skin {
+            Ok(skin) => skins.push(skin),
+            Err(err) => eprintln!("ignoring skin {}", err),
+        }
+    }
+    front.set_skins(
+        skins,
+        matches
+            .value_of("skin")
+            .or_else(|| config.skin.as_ref().map(|x| &x[..])),
+    );
+
+    // reloaded whenever the player switches skins
+    let mut loaded_skin = front.get_skin().clone();
+    let mut glyphs = frontend::load_glyphs(&mut window, &assets, &loaded_skin);
+    let mut icons = frontend::Icons::load(&mut window, &assets, &loaded_skin);
 
     window.set_max_fps(30);
 
     while let Some(e) = window.next() {
-        if let Some(_) = e.render_args() {
+        if *front.get_skin() != loaded_skin {
+            loaded_skin = front.get_skin().clone();
+            glyphs = frontend::load_glyphs(&mut window, &assets, &loaded_skin);
+            icons = frontend::Icons::load(&mut window, &assets, &loaded_skin);
+        }
+
+        if front.get_title() != title {
+            title = front.get_title();
+            window.set_title(title.clone());
+        }
+
+        if e.render_args().is_some() {
             front.draw(&mut window, &e, &mut glyphs, &icons);
         }
 
+        if e.update_args().is_some() {
+            front.update();
+        }
+
+        if let Some([width, height]) = e.resize_args() {
+            front.handle_resize(width, height);
+        }
+
+        if let Some(focused) = e.focus_args() {
+            front.handle_focus(focused);
+        }
+
+        if let Some(text) = e.text_args() {
+            front.handle_text(&text);
+        }
+
         if let Some(mouse_e) = e.mouse_cursor_args() {
             front.handle_mouse_position(mouse_e[0], mouse_e[1]);
         }
@@ -129,11 +374,227 @@ fn main() {
             }
         }
 
-        if let Some(button) = e.release_args() {
-            match button {
-                Button::Mouse(m) => front.handle_mouse_click(m),
-                _ => (),
-            }
+        if let Some(Button::Mouse(m)) = e.release_args() {
+            front.handle_mouse_click(m, &mut window);
         }
     }
 }
+
+// the built-in presets, classic unless the command line or the config asks for another set
+fn get_preset_set(matches: &ArgMatches, config: &Config) -> PresetRegistry {
+    let name = matches
+        .value_of("presets")
+        .or_else(|| config.preset_set.as_ref().map(|x| &x[..]))
+        .unwrap_or("classic");
+
+    match PresetRegistry::from_set_name(name) {
+        Some(presets) => presets,
+        None => panic!("invalid preset set!"),
+    }
+}
+
+// the built-in presets, then Custom, then the presets from the config in their order
+fn get_presets(matches: &ArgMatches, config: &Config) -> PresetRegistry {
+    let mut presets = get_preset_set(matches, config);
+
+    let rows = matches
+        .value_of("rows")
+        .map(|rows| rows.parse::<u32>().unwrap())
+        .unwrap_or(config.custom.rows);
+    let cols = matches
+        .value_of("cols")
+        .map(|cols| cols.parse::<u32>().unwrap())
+        .unwrap_or(config.custom.cols);
+    let max_mines = matches
+        .value_of("mines")
+        .map(|mines| mines.parse::<u32>().unwrap())
+        .unwrap_or(config.custom.mines);
+    presets.register(Preset::new("Custom", cols, rows, min(max_mines, rows * cols)));
+
+    for preset in &config.presets {
+        presets.register(Preset::new(
+            &preset.name,
+            preset.cols,
+            preset.rows,
+            min(preset.mines, preset.rows * preset.cols),
+        ));
+    }
+
+    presets
+}
+
+// presets are picked by their number starting from 1 or by name
+fn get_preset_index(matches: &ArgMatches, presets: &PresetRegistry) -> usize {
+    let level = matches.value_of("difficulty").unwrap_or("1");
+
+    match level.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= presets.len() => number - 1,
+        Ok(_) => panic!("invalid difficulty level!"),
+        Err(_) => match presets.position(level) {
+            Some(index) => index,
+            None => panic!("invalid difficulty level!"),
+        },
+    }
+}
+
+fn get_lives(matches: &ArgMatches, default: u32) -> u32 {
+    let lives = matches
+        .value_of("lives")
+        .map(|lives| lives.parse::<u32>().unwrap())
+        .unwrap_or(default);
+
+    if lives == 0 {
+        panic!("need at least one life!");
+    }
+
+    lives
+}
+
+fn get_time_limit(matches: &ArgMatches, default: Option<u64>) -> Option<u64> {
+    let time_limit = matches
+        .value_of("time-limit")
+        .map(|limit| limit.parse::<u64>().unwrap())
+        .or(default);
+
+    if time_limit.is_some_and(|limit| limit > MAX_TIME) {
+        panic!("time limit over {} seconds!", MAX_TIME);
+    }
+
+    time_limit
+}
+
+fn serve(matches: &ArgMatches, config: &Config) {
+    let presets = get_presets(matches, config);
+    let preset = presets.get(get_preset_index(matches, &presets)).unwrap();
+    let (cols, rows, num_mines) = (preset.cols, preset.rows, preset.mines);
+    let port = matches
+        .value_of("port")
+        .unwrap_or("7878")
+        .parse::<u16>()
+        .unwrap();
+    let seed = matches
+        .value_of("seed")
+        .map(|seed| seed.parse::<u64>().unwrap())
+        .unwrap_or_else(rand::random);
+
+    if matches.is_present("coop") {
+        let board = CoopBoard {
+            cols,
+            rows,
+            num_mines,
+            seed,
+        };
+
+        let server = CoopServer::bind(("0.0.0.0", port), board).unwrap();
+        println!(
+            "coop server on {}, {}x{} with {} mines",
+            server.local_addr().unwrap(),
+            cols,
+            rows,
+            num_mines
+        );
+        server.run().unwrap();
+        return;
+    }
+
+    let board = RaceBoard {
+        cols,
+        rows,
+        num_mines,
+        lives: get_lives(matches, config.rules.get_rules().lives),
+        seed,
+    };
+
+    let server = RaceServer::bind(("0.0.0.0", port), board).unwrap();
+    println!(
+        "race server on {}, {}x{} with {} mines, seed {}",
+        server.local_addr().unwrap(),
+        cols,
+        rows,
+        num_mines,
+        seed
+    );
+    server.run().unwrap();
+}
+
+// the layout file drawn with the config's palette and the window's assets
+fn render_board(matches: &ArgMatches, config: &Config) {
+    let path = matches.value_of("board").unwrap();
+    let mut layout = String::new();
+    if path == "-" {
+        io::stdin().read_to_string(&mut layout).unwrap();
+    } else {
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut layout))
+            .unwrap();
+    }
+
+    let rules = Rules {
+        lives: get_lives(matches, config.rules.get_rules().lives),
+        ..Rules::default()
+    };
+    let game = MineSweeper::from_layout(&layout, rules)
+        .unwrap_or_else(|err| panic!("invalid board: {}", err));
+
+    let palette = match matches
+        .value_of("palette")
+        .or_else(|| config.palette.as_ref().map(|x| &x[..]))
+    {
+        Some(name) => match palette::find_palette(name) {
+            Some(index) => palette::PALETTES[index],
+            None => panic!("invalid palette!"),
+        },
+        None => palette::DEFAULT,
+    };
+
+    let options = RenderOptions {
+        scale: matches
+            .value_of("scale")
+            .map(|scale| scale.parse::<u32>().unwrap())
+            .unwrap_or(1),
+        palette,
+        high_contrast: matches.is_present("high-contrast") || config.high_contrast,
+        patterns: matches.is_present("patterns") || config.patterns,
+        view: match matches.value_of("view").unwrap_or("board") {
+            "board" => View::Board,
+            "puzzle" => View::Puzzle,
+            "solution" => View::Solution,
+            _ => panic!("invalid view!"),
+        },
+        misflags: matches.is_present("misflags"),
+        time_ms: matches.value_of("time").map(|ms| ms.parse::<u64>().unwrap()),
+    };
+
+    let out = Path::new(matches.value_of("out").unwrap());
+    if out.extension().is_some_and(|x| x.eq_ignore_ascii_case("svg")) {
+        svg::save_svg(&game, &options, out).unwrap();
+    } else {
+        let assets = find_folder::Search::ParentsThenKids(3, 3)
+            .for_folder("assets")
+            .unwrap();
+        Renderer::load(&assets)
+            .and_then(|renderer| renderer.save_png(&game, &options, out))
+            .unwrap();
+    }
+}
+
+fn run_tournament<P: Player>(player: &mut P, presets: &PresetRegistry, games: u32) {
+    println!("player: {}", player.name());
+    println!(
+        "{:<14}{:>8}{:>10}{:>14}{:>10}{:>10}",
+        "difficulty", "games", "win rate", "avg time ms", "guesses", "3BV/s"
+    );
+
+    for preset in presets.presets() {
+        let report = bot::run_tournament(player, preset.cols, preset.rows, preset.mines, games);
+        println!(
+            "{:<14}{:>8}{:>9.1}%{:>14.2}{:>10.2}{:>10.1}",
+            preset.name,
+            report.games,
+            report.get_win_rate() * 100.0,
+            report.get_average_time_ms(),
+            report.get_average_guesses(),
+            report.get_average_bbbv_per_sec()
+        );
+    }
+}
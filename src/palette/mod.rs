@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests;
+
+use config::ThemeConfig;
+
+// [red, green, blue, alpha] from 0 to 1
+pub type Color = [f32; 4];
+
+// colors of the numbers 1 to 8 and of revealed mines, the parts of the board that are told
+// apart by color alone
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Palette {
+    pub name: &'static str,
+    pub numbers: [Color; 8],
+    pub mine_revealed: Color,
+    pub mine_border: Color,
+}
+
+// the colors of Windows Minesweeper
+pub const DEFAULT: Palette = Palette {
+    name: "Default",
+    numbers: [
+        [0.0, 0.0, 1.0, 1.0],
+        [0.13, 0.54, 0.13, 1.0],
+        [1.0, 0.0, 0.0, 1.0],
+        [0.5, 0.0, 0.5, 1.0],
+        [0.5, 0.0, 0.0, 1.0],
+        [0.0, 1.0, 1.0, 1.0],
+        [0.0, 0.0, 0.0, 1.0],
+        [0.5, 0.5, 0.5, 1.0],
+    ],
+    mine_revealed: [0.7, 0.0, 0.0, 1.0],
+    mine_border: [0.8, 0.0, 0.0, 1.0],
+};
+
+// Okabe and Ito's, safe for red-green color blindness
+pub const OKABE_ITO: Palette = Palette {
+    name: "Okabe-Ito",
+    numbers: [
+        [0.0, 0.45, 0.7, 1.0],
+        [0.0, 0.62, 0.45, 1.0],
+        [0.84, 0.37, 0.0, 1.0],
+        [0.8, 0.47, 0.65, 1.0],
+        [0.9, 0.62, 0.0, 1.0],
+        [0.34, 0.71, 0.91, 1.0],
+        [0.0, 0.0, 0.0, 1.0],
+        [0.47, 0.47, 0.47, 1.0],
+    ],
+    mine_revealed: [0.9, 0.62, 0.0, 1.0],
+    mine_border: [0.0, 0.0, 0.0, 1.0],
+};
+
+// Paul Tol's vibrant scheme, safe for every common color blindness
+pub const TOL: Palette = Palette {
+    name: "Tol",
+    numbers: [
+        [0.0, 0.47, 0.73, 1.0],
+        [0.0, 0.6, 0.53, 1.0],
+        [0.8, 0.2, 0.07, 1.0],
+        [0.93, 0.2, 0.47, 1.0],
+        [0.93, 0.47, 0.2, 1.0],
+        [0.2, 0.73, 0.93, 1.0],
+        [0.0, 0.0, 0.0, 1.0],
+        [0.47, 0.47, 0.47, 1.0],
+    ],
+    mine_revealed: [0.93, 0.47, 0.2, 1.0],
+    mine_border: [0.0, 0.0, 0.0, 1.0],
+};
+
+// in the order they are cycled through, the default first
+pub const PALETTES: [Palette; 3] = [DEFAULT, OKABE_ITO, TOL];
+
+// names match regardless of case
+pub fn find_palette(name: &str) -> Option<usize> {
+    PALETTES
+        .iter()
+        .position(|x| x.name.eq_ignore_ascii_case(name))
+}
+
+pub fn get_number_color(num: u32) -> Color {
+    match num {
+        1..=8 => DEFAULT.numbers[num as usize - 1],
+        _ => [0.0, 0.0, 0.0, 1.0],
+    }
+}
+
+// black and white with yellow text, layered over the skin and palette, the numbers keep the
+// palette's colors on white cells
+pub fn get_high_contrast_theme() -> ThemeConfig {
+    ThemeConfig {
+        background: Some([0.0, 0.0, 0.0, 1.0]),
+        ui_rect: Some([0.0, 0.0, 0.0, 1.0]),
+        ui_text: Some([1.0, 1.0, 0.0, 1.0]),
+        cell_covered: Some([0.35, 0.35, 0.35, 1.0]),
+        cell_revealed: Some([1.0, 1.0, 1.0, 1.0]),
+        cell_border: Some([0.0, 0.0, 0.0, 1.0]),
+        bevel_light: Some([0.8, 0.8, 0.8, 1.0]),
+        bevel_shadow: Some([0.0, 0.0, 0.0, 1.0]),
+        ..ThemeConfig::default()
+    }
+}
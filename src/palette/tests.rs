@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn test_find_palette() {
+    assert_eq!(find_palette("default"), Some(0));
+    assert_eq!(find_palette("OKABE-ITO"), Some(1));
+    assert_eq!(find_palette("Tol"), Some(2));
+    assert_eq!(find_palette("rainbow"), None);
+    assert_eq!(PALETTES[0], DEFAULT);
+}
+
+#[test]
+fn test_palettes() {
+    for palette in &PALETTES {
+        // every number needs a color of its own
+        for (i, color) in palette.numbers.iter().enumerate() {
+            assert!(!palette.numbers[i + 1..].contains(color), "{}", palette.name);
+        }
+        assert!(palette.mine_revealed != palette.mine_border, "{}", palette.name);
+    }
+
+    assert_eq!(get_number_color(1), [0.0, 0.0, 1.0, 1.0]);
+    assert_eq!(get_number_color(8), [0.5, 0.5, 0.5, 1.0]);
+    assert_eq!(get_number_color(9), [0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_high_contrast_theme() {
+    let theme = get_high_contrast_theme();
+    assert_eq!(theme.cell_revealed, Some([1.0, 1.0, 1.0, 1.0]));
+    // the palette decides these
+    assert_eq!(theme.numbers, None);
+    assert_eq!(theme.mine_revealed, None);
+}
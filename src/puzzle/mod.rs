@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod tests;
+
+use std::cmp::max;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use dirs;
+use rand::Rng;
+
+use game::layout::LayoutError;
+use game::preset::Preset;
+use game::{self, GameState, MineSweeper, Position, Rules, SquareState};
+use solver::{self, Reason};
+
+// boards tried before giving up on a rating that does not suit the size
+pub const MAX_ATTEMPTS: u32 = 100;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Rating {
+    // every step follows from a single number
+    Easy,
+    // some steps take two numbers together or the count of mines left
+    Medium,
+    // some steps take weighing every way the mines could lie
+    Hard,
+}
+
+pub const RATINGS: [(&str, Rating); 3] = [
+    ("easy", Rating::Easy),
+    ("medium", Rating::Medium),
+    ("hard", Rating::Hard),
+];
+
+impl Rating {
+    pub fn parse(name: &str) -> Option<Rating> {
+        RATINGS
+            .iter()
+            .find(|x| x.0.eq_ignore_ascii_case(name))
+            .map(|x| x.1)
+    }
+
+    pub fn get_name(self) -> &'static str {
+        RATINGS.iter().find(|x| x.1 == self).unwrap().0
+    }
+
+    fn from_reason(reason: &Reason) -> Rating {
+        match *reason {
+            Reason::Single(_) => Rating::Easy,
+            Reason::Pair(_, _) | Reason::MineCount => Rating::Medium,
+            Reason::Enumeration => Rating::Hard,
+        }
+    }
+}
+
+// plays the board on a copy with every deduction no harder than the rating allows, the
+// copy as far as it got and the hardest deduction it took
+fn play_deductions(game: &MineSweeper, rating: Rating) -> (MineSweeper, Rating) {
+    let mut game = MineSweeper::from_layout(&game.get_layout(), Rules::default()).unwrap();
+    let mut hardest = Rating::Easy;
+
+    while game.state == GameState::Ongoing {
+        let deductions: Vec<_> = solver::find_deductions(&game.get_visible_board(), game.num_mines)
+            .into_iter()
+            .filter(|x| Rating::from_reason(&x.reason) <= rating)
+            .collect();
+        if deductions.is_empty() {
+            break;
+        }
+
+        for deduction in deductions {
+            hardest = max(hardest, Rating::from_reason(&deduction.reason));
+            if game.get_square(deduction.pos.0, deduction.pos.1).state != SquareState::Covered {
+                continue;
+            }
+
+            if deduction.is_mine {
+                game.toggle_flag_square(&deduction.pos);
+            } else {
+                game.reveal_square(&deduction.pos);
+            }
+        }
+        game.update_game_state();
+    }
+
+    (game, hardest)
+}
+
+// the rating of the hardest step clearing the board takes from where it stands, None when it
+// can not be cleared without a guess
+pub fn rate(game: &MineSweeper) -> Option<Rating> {
+    match play_deductions(game, Rating::Hard) {
+        (ref solved, rating) if solved.state == GameState::Won => Some(rating),
+        _ => None,
+    }
+}
+
+// a board of the size opened around a blank square, with safe squares revealed wherever
+// deduction alone gets stuck until it clears with exactly the rating's reasoning, the same seed
+// always gives the same puzzle, None if no board tried needs the rating
+pub fn generate(
+    cols: u32,
+    rows: u32,
+    mines: u32,
+    rating: Rating,
+    seed: u64,
+) -> Option<MineSweeper> {
+    let mut rng = game::get_seeded_rng(seed);
+
+    for _ in 0..MAX_ATTEMPTS {
+        let board = MineSweeper::with_seed(cols, rows, mines, Rules::default(), rng.gen());
+        let squares: Vec<_> = (0..rows * cols)
+            .map(|x| board.get_square(x / cols, x % cols))
+            .collect();
+
+        let blanks: Vec<_> = (0..rows * cols)
+            .filter(|&x| !squares[x as usize].is_mine && squares[x as usize].adjacent_mines == 0)
+            .collect();
+        if blanks.is_empty() {
+            continue;
+        }
+        let mines_index: Vec<_> = (0..squares.len()).filter(|&x| squares[x].is_mine).collect();
+
+        let mask = vec![false; squares.len()];
+        let mut puzzle = MineSweeper::with_mask(cols, rows, mines_index, &mask, Rules::default());
+        let start = blanks[rng.gen_range(0, blanks.len() as u32) as usize];
+        puzzle.reveal_square(&Position(start / cols, start % cols));
+        puzzle.first_moved();
+
+        loop {
+            let (stuck, hardest) = play_deductions(&puzzle, rating);
+            if stuck.state == GameState::Won {
+                if hardest == rating {
+                    return Some(puzzle);
+                }
+                break;
+            }
+
+            // a safe square where the solver got stuck, next to what it uncovered if it can
+            let covered: Vec<_> = (0..rows * cols)
+                .map(|x| Position(x / cols, x % cols))
+                .filter(|pos| {
+                    let square = stuck.get_square(pos.0, pos.1);
+                    square.state == SquareState::Covered && !square.is_mine
+                })
+                .collect();
+            let board = stuck.get_visible_board();
+            let frontier: Vec<_> = covered
+                .iter()
+                .cloned()
+                .filter(|pos| {
+                    solver::get_neighbors(&board, pos).iter().any(|x| {
+                        stuck.get_square(x.0, x.1).state == SquareState::Revealed
+                    })
+                })
+                .collect();
+            let hints = if frontier.is_empty() { covered } else { frontier };
+
+            let hint = hints[rng.gen_range(0, hints.len() as u32) as usize];
+            puzzle.reveal_square(&hint);
+        }
+    }
+
+    None
+}
+
+// a puzzle's name in the progress file, its starting layout on one line
+pub fn get_key(game: &MineSweeper) -> String {
+    game.get_layout().replace('\n', "/")
+}
+
+// generated puzzles go by their board and number instead, so finding the first one not solved
+// does not generate every one before it
+pub fn get_generated_key(preset: &Preset, rating: Rating, number: u64) -> String {
+    format!(
+        "{} {}x{} {} {} #{}",
+        preset.name,
+        preset.cols,
+        preset.rows,
+        preset.mines,
+        rating.get_name(),
+        number + 1
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoadError {
+    // puzzles are numbered from 1 in the order they appear in the file
+    Layout(usize, LayoutError),
+    NeedsGuess(usize),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::Layout(number, err) => write!(f, "puzzle {}: {}", number, err),
+            LoadError::NeedsGuess(number) => {
+                write!(f, "puzzle {} can not be solved without a guess", number)
+            }
+        }
+    }
+}
+
+// layouts separated by blank lines, each with its mines written out, and every one has to
+// clear by deduction alone
+pub fn parse_puzzles(text: &str) -> Result<Vec<(MineSweeper, Rating)>, LoadError> {
+    let mut puzzles = Vec::new();
+    let mut layout = String::new();
+
+    // one blank line after the last layout ends it like the others
+    for line in text.lines().chain(Some("")) {
+        if !line.trim().is_empty() {
+            layout.push_str(line);
+            layout.push('\n');
+            continue;
+        }
+        if layout.is_empty() {
+            continue;
+        }
+
+        let number = puzzles.len() + 1;
+        let game = MineSweeper::from_layout(&layout, Rules::default())
+            .map_err(|err| LoadError::Layout(number, err))?;
+        let rating = rate(&game).ok_or(LoadError::NeedsGuess(number))?;
+
+        puzzles.push((game, rating));
+        layout.clear();
+    }
+
+    Ok(puzzles)
+}
+
+pub fn load_puzzles(path: &Path) -> io::Result<Vec<(MineSweeper, Rating)>> {
+    let mut text = String::new();
+    File::open(path)?.read_to_string(&mut text)?;
+
+    parse_puzzles(&text).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), err),
+        )
+    })
+}
+
+// the generated puzzles for the preset and rating, numbered from 0, the same every time
+pub fn get_generated_puzzle(preset: &Preset, rating: Rating, number: u64) -> Option<MineSweeper> {
+    generate(preset.cols, preset.rows, preset.mines, rating, number)
+}
+
+pub fn get_default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("minesweep-rs").join("puzzles.txt"))
+}
+
+// the puzzles solved so far, one key per line
+#[derive(Default)]
+pub struct PuzzleProgress {
+    solved: BTreeSet<String>,
+}
+
+impl PuzzleProgress {
+    // a missing file is no progress
+    pub fn load(path: &Path) -> io::Result<PuzzleProgress> {
+        let mut progress = PuzzleProgress::default();
+
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(progress),
+            Err(err) => return Err(err),
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                progress.solved.insert(line.trim().to_owned());
+            }
+        }
+
+        Ok(progress)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = File::create(path)?;
+        for key in &self.solved {
+            writeln!(file, "{}", key)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_solved(&self, key: &str) -> bool {
+        self.solved.contains(key)
+    }
+
+    // false if it was solved before
+    pub fn add(&mut self, key: &str) -> bool {
+        self.solved.insert(key.to_owned())
+    }
+}
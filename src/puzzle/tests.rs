@@ -0,0 +1,63 @@
+use super::*;
+
+use std::env;
+
+#[test]
+fn test_rating() {
+    assert_eq!(Rating::parse("Medium"), Some(Rating::Medium));
+    assert_eq!(Rating::parse("impossible"), None);
+    assert_eq!(Rating::Hard.get_name(), "hard");
+    assert!(Rating::Easy < Rating::Hard);
+}
+
+#[test]
+fn test_generate() {
+    for &(_, rating) in &RATINGS {
+        let puzzle = generate(8, 8, 10, rating, 7).unwrap();
+
+        assert_eq!(puzzle.state, GameState::Ongoing);
+        assert!(puzzle.is_started());
+        assert_eq!(puzzle.get_flags_left(), 10);
+        assert_eq!(rate(&puzzle), Some(rating));
+        assert_eq!(get_key(&generate(8, 8, 10, rating, 7).unwrap()), get_key(&puzzle));
+    }
+
+    let preset = Preset::new("Beginner", 9, 9, 10);
+    let first = get_generated_puzzle(&preset, Rating::Easy, 0).unwrap();
+    let second = get_generated_puzzle(&preset, Rating::Easy, 1).unwrap();
+    assert!(get_key(&first) != get_key(&second));
+    assert_eq!(get_generated_key(&preset, Rating::Easy, 1), "Beginner 9x9 10 easy #2");
+}
+
+#[test]
+fn test_parse() {
+    let puzzles = parse_puzzles("\n1*\n11\n\n\n..1#\n..1*\n..11\n").unwrap();
+    assert_eq!(puzzles.len(), 2);
+    assert_eq!(puzzles[0].1, Rating::Easy);
+    assert_eq!(puzzles[1].1, Rating::Easy);
+    assert_eq!(get_key(&puzzles[1].0), "..1#/..1*/..11");
+
+    // the two squares next to the ones are as likely to hide the mine
+    assert_eq!(parse_puzzles("1*\n11\n\n1*\n1#").err(), Some(LoadError::NeedsGuess(2)));
+    assert_eq!(
+        parse_puzzles("1*\n11\n\n#?").err(),
+        Some(LoadError::Layout(2, LayoutError::UnknownSquare('?', Position(0, 1))))
+    );
+}
+
+#[test]
+fn test_progress() {
+    let path = env::temp_dir().join(format!("minesweep-rs-puzzles-{}.txt", ::std::process::id()));
+
+    let mut progress = PuzzleProgress::default();
+    assert!(progress.add(".1*#/.1##"));
+    assert!(!progress.add(".1*#/.1##"));
+    progress.save(&path).unwrap();
+
+    let loaded = PuzzleProgress::load(&path).unwrap();
+    assert!(loaded.is_solved(".1*#/.1##"));
+    assert!(!loaded.is_solved("1*/11"));
+
+    fs::remove_file(&path).unwrap();
+    assert!(!PuzzleProgress::load(&path).unwrap().is_solved(".1*#/.1##"));
+}
@@ -0,0 +1,332 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use game::{GameState, MineSweeper, Rules};
+
+// line based race protocol, every player gets the same seeded board
+//
+//   client -> server   hello <name>
+//   server -> client   welcome <id> <cols> <rows> <mines> <lives> <seed>
+//   client -> server   progress <percent> <explosions> <status>
+//   server -> all      player <id> <percent> <explosions> <status> <name>
+//   server -> all      left <id>
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RaceBoard {
+    pub cols: u32,
+    pub rows: u32,
+    pub num_mines: u32,
+    pub lives: u32,
+    pub seed: u64,
+}
+
+impl RaceBoard {
+    pub fn new_game(&self) -> MineSweeper {
+        let rules = Rules {
+            lives: self.lives,
+            ..Rules::default()
+        };
+        MineSweeper::with_seed(self.cols, self.rows, self.num_mines, rules, self.seed)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RaceStatus {
+    pub id: u32,
+    pub name: String,
+    pub progress: u32,
+    pub explosions: u32,
+    pub state: GameState,
+}
+
+impl RaceStatus {
+    fn to_line(&self) -> String {
+        format!(
+            "player {} {} {} {} {}",
+            self.id,
+            self.progress,
+            self.explosions,
+            get_status_name(self.state),
+            self.name
+        )
+    }
+
+    fn from_line(line: &str) -> Option<RaceStatus> {
+        let mut words = line.splitn(6, ' ');
+        if words.next() != Some("player") {
+            return None;
+        }
+
+        let id = words.next()?.parse().ok()?;
+        let progress = words.next()?.parse().ok()?;
+        let explosions = words.next()?.parse().ok()?;
+        let state = parse_status(words.next()?)?;
+        let name = words.next().unwrap_or("").to_owned();
+
+        Some(RaceStatus {
+            id,
+            name,
+            progress,
+            explosions,
+            state,
+        })
+    }
+}
+
+fn get_status_name(state: GameState) -> &'static str {
+    match state {
+        GameState::Ongoing => "ongoing",
+        GameState::Won => "won",
+        GameState::Lost => "lost",
+    }
+}
+
+fn parse_status(word: &str) -> Option<GameState> {
+    match word {
+        "ongoing" => Some(GameState::Ongoing),
+        "won" => Some(GameState::Won),
+        "lost" => Some(GameState::Lost),
+        _ => None,
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+struct Lobby {
+    streams: Vec<(u32, TcpStream)>,
+    players: Vec<RaceStatus>,
+}
+
+impl Lobby {
+    fn broadcast(&mut self, line: &str) {
+        // a player that dropped out is cleaned up by its own connection thread
+        for (_, stream) in &mut self.streams {
+            let _ = writeln!(stream, "{}", line);
+        }
+    }
+}
+
+pub struct RaceServer {
+    listener: TcpListener,
+    board: RaceBoard,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl RaceServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A, board: RaceBoard) -> io::Result<RaceServer> {
+        Ok(RaceServer {
+            listener: TcpListener::bind(addr)?,
+            board,
+            lobby: Arc::new(Mutex::new(Lobby {
+                streams: Vec::new(),
+                players: Vec::new(),
+            })),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // accepts players until the listener fails, each one is served on its own thread
+    pub fn run(&self) -> io::Result<()> {
+        for (id, stream) in self.listener.incoming().enumerate() {
+            let stream = stream?;
+            let board = self.board;
+            let lobby = self.lobby.clone();
+
+            thread::spawn(move || {
+                let _ = RaceServer::serve_player(id as u32, stream, board, &lobby);
+
+                let mut lobby = lobby.lock().unwrap();
+                lobby.streams.retain(|&(x, _)| x != id as u32);
+                lobby.players.retain(|x| x.id != id as u32);
+                lobby.broadcast(&format!("left {}", id));
+            });
+        }
+
+        Ok(())
+    }
+
+    fn serve_player(
+        id: u32,
+        stream: TcpStream,
+        board: RaceBoard,
+        lobby: &Mutex<Lobby>,
+    ) -> io::Result<()> {
+        let mut writer = stream.try_clone()?;
+        let mut lines = BufReader::new(stream).lines();
+
+        let hello = lines.next().unwrap_or_else(|| Err(invalid_data("no hello")))?;
+        let name = match hello.strip_prefix("hello") {
+            Some(name) => name.trim().to_owned(),
+            None => return Err(invalid_data("expected hello")),
+        };
+
+        let mut status = RaceStatus {
+            id,
+            name,
+            progress: 0,
+            explosions: 0,
+            state: GameState::Ongoing,
+        };
+
+        {
+            let mut lobby = lobby.lock().unwrap();
+            writeln!(
+                writer,
+                "welcome {} {} {} {} {} {}",
+                id, board.cols, board.rows, board.num_mines, board.lives, board.seed
+            )?;
+            for player in &lobby.players {
+                writeln!(writer, "{}", player.to_line())?;
+            }
+
+            lobby.streams.push((id, writer));
+            lobby.players.push(status.clone());
+            lobby.broadcast(&status.to_line());
+        }
+
+        for line in lines {
+            let line = line?;
+            let words: Vec<_> = line.split_whitespace().collect();
+
+            // anything else is ignored so newer clients can talk to older servers
+            if words.len() == 4 && words[0] == "progress" {
+                match (words[1].parse(), words[2].parse(), parse_status(words[3])) {
+                    (Ok(progress), Ok(explosions), Some(state)) => {
+                        status.progress = progress;
+                        status.explosions = explosions;
+                        status.state = state;
+                    }
+                    _ => continue,
+                }
+
+                let mut lobby = lobby.lock().unwrap();
+                for player in lobby.players.iter_mut().filter(|x| x.id == id) {
+                    *player = status.clone();
+                }
+                lobby.broadcast(&status.to_line());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RaceClient {
+    pub id: u32,
+    pub board: RaceBoard,
+    stream: TcpStream,
+    players: Arc<Mutex<Vec<RaceStatus>>>,
+    last_sent: Option<(u32, u32, GameState)>,
+}
+
+impl RaceClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<RaceClient> {
+        let mut stream = TcpStream::connect(addr)?;
+        writeln!(stream, "hello {}", name)?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut welcome = String::new();
+        reader.read_line(&mut welcome)?;
+
+        let words: Vec<_> = welcome.split_whitespace().collect();
+        if words.len() != 7 || words[0] != "welcome" {
+            return Err(invalid_data("expected welcome"));
+        }
+
+        let numbers: Vec<u64> = words[1..]
+            .iter()
+            .map(|x| x.parse().map_err(|_| invalid_data("bad welcome")))
+            .collect::<io::Result<_>>()?;
+
+        let board = RaceBoard {
+            cols: numbers[1] as u32,
+            rows: numbers[2] as u32,
+            num_mines: numbers[3] as u32,
+            lives: numbers[4] as u32,
+            seed: numbers[5],
+        };
+
+        let players = Arc::new(Mutex::new(Vec::new()));
+        let shared = players.clone();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                let mut players = shared.lock().unwrap();
+                if let Some(status) = RaceStatus::from_line(&line) {
+                    players.retain(|x: &RaceStatus| x.id != status.id);
+                    players.push(status);
+                    players.sort_by_key(|x| x.id);
+                } else if let Some(id) = line.strip_prefix("left ") {
+                    let id = id.trim().parse::<u32>().ok();
+                    players.retain(|x| Some(x.id) != id);
+                }
+            }
+        });
+
+        Ok(RaceClient {
+            id: numbers[0] as u32,
+            board,
+            stream,
+            players,
+            last_sent: None,
+        })
+    }
+
+    // reports the game to the server, only when something changed since the last report
+    pub fn send_progress(&mut self, game: &MineSweeper) -> io::Result<()> {
+        let last_progress = self.last_sent.map_or(0, |(progress, _, _)| progress);
+        // a lost game has every square revealed, so the progress stays where it was
+        let progress = match game.state {
+            GameState::Ongoing => game.get_progress(),
+            GameState::Won => 100,
+            GameState::Lost => last_progress,
+        };
+
+        let report = (progress, game.get_explosions(), game.state);
+        if self.last_sent == Some(report) {
+            return Ok(());
+        }
+
+        writeln!(
+            self.stream,
+            "progress {} {} {}",
+            progress,
+            game.get_explosions(),
+            get_status_name(game.state)
+        )?;
+        self.last_sent = Some(report);
+
+        Ok(())
+    }
+
+    pub fn get_opponents(&self) -> Vec<RaceStatus> {
+        self.players
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|x| x.id != self.id)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for RaceClient {
+    fn drop(&mut self) {
+        // the reader thread holds its own handle, so the socket has to be closed explicitly
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
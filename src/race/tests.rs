@@ -0,0 +1,83 @@
+use super::*;
+
+use game::Position;
+
+use std::time::{Duration, Instant};
+
+fn wait_for_opponent<F: Fn(&RaceStatus) -> bool>(client: &RaceClient, check: F) -> RaceStatus {
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    loop {
+        if let Some(status) = client.get_opponents().into_iter().find(|x| check(x)) {
+            return status;
+        }
+
+        assert!(Instant::now() < deadline, "opponent update never arrived");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn test_status_line() {
+    let status = RaceStatus {
+        id: 3,
+        name: "mine hunter".to_owned(),
+        progress: 42,
+        explosions: 1,
+        state: GameState::Lost,
+    };
+
+    assert_eq!(status.to_line(), "player 3 42 1 lost mine hunter");
+    assert_eq!(RaceStatus::from_line(&status.to_line()), Some(status));
+    assert_eq!(RaceStatus::from_line("player 3 42 1 asleep x"), None);
+    assert_eq!(RaceStatus::from_line("left 3"), None);
+}
+
+#[test]
+fn test_race() {
+    let board = RaceBoard {
+        cols: 16,
+        rows: 16,
+        num_mines: 40,
+        lives: 2,
+        seed: 99,
+    };
+    let server = RaceServer::bind("127.0.0.1:0", board).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut alice = RaceClient::connect(addr, "alice").unwrap();
+    let bob = RaceClient::connect(addr, "bob").unwrap();
+
+    assert_eq!(alice.board, board);
+    assert_eq!(bob.board, board);
+    assert_ne!(alice.id, bob.id);
+
+    // both layouts come out identical from the shared seed
+    let mut game = alice.board.new_game();
+    assert_eq!(game.get_layout(), bob.board.new_game().get_layout());
+
+    let joined = wait_for_opponent(&bob, |x| x.name == "alice");
+    assert_eq!(joined.progress, 0);
+    assert_eq!(joined.state, GameState::Ongoing);
+
+    game.reveal_square(&Position(8, 8));
+    game.first_moved();
+    game.update_game_state();
+    alice.send_progress(&game).unwrap();
+
+    let progress = game.get_progress();
+    let update = wait_for_opponent(&bob, |x| x.name == "alice" && x.progress == progress);
+    assert_eq!(update.id, alice.id);
+    assert_eq!(update.explosions, 0);
+
+    let seen = wait_for_opponent(&alice, |x| x.name == "bob");
+    assert_eq!(seen.id, bob.id);
+
+    drop(alice);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !bob.get_opponents().is_empty() {
+        assert!(Instant::now() < deadline, "left message never arrived");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
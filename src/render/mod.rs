@@ -0,0 +1,646 @@
+#[cfg(test)]
+mod tests;
+
+pub mod svg;
+
+use std::cmp::min;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use image::{self, Rgba, RgbaImage};
+use rusttype::{point, Font, FontCollection, Scale};
+
+use game::{GameState, LoseReason, MineSweeper, Square, SquareState};
+use palette::{self, Color, Palette};
+
+// the window's layout at zoom 1, in pixels, which the window draws with as well
+pub const TOP_BAR_HEIGHT: u32 = 27;
+pub const SQUARE_SIZE: u32 = 20;
+pub const MARGIN: f64 = 2.0;
+pub const UI_FONT_SIZE: u32 = 40;
+pub const UI_FONT_Y_OFFSET: f64 = 22.0;
+pub const UI_RECT_HEIGHT: f64 = TOP_BAR_HEIGHT as f64 - 2.0 * MARGIN;
+pub const NUMBER_FONT_SIZE: u32 = 23;
+pub const TIME_FONT_SIZE: u32 = 30;
+pub const TIME_RECT_MIN_WIDTH: f64 = UI_FONT_SIZE as f64 * 1.5;
+pub const HEART_SIZE: f64 = 10.0;
+pub const HEART_FONT_SIZE: u32 = 30;
+// icons are scaled to these sizes in pixels whatever the size of the skin's textures
+pub const FACE_ICON_SIZE: f64 = 17.92;
+pub const MINE_ICON_SIZE: f64 = 14.0;
+pub const FLAG_ICON_SIZE: f64 = 12.8;
+pub const PATTERN_SIZE: f64 = 6.0;
+pub const PATTERN_LINE_RADIUS: f64 = 0.75;
+pub const HUNT_TURN_BORDER_RADIUS: f64 = 1.0;
+pub const MISFLAG_INSET: f64 = 1.5;
+pub const MISFLAG_LINE_RADIUS: f64 = 1.0;
+
+pub const BACKGROUND_COLOR: Color = [0.5, 0.5, 0.5, 1.0];
+pub const CELL_BORDER_COLOR: Color = [0.8, 0.8, 0.8, 1.0];
+pub const CELL_COVERED_COLOR: Color = [0.9, 0.9, 0.9, 1.0];
+pub const CELL_REVEALED_COLOR: Color = [0.7, 0.7, 0.7, 1.0];
+pub const UI_RECT_COLOR: Color = [0.3, 0.3, 0.3, 1.0];
+pub const UI_TEXT_COLOR: Color = [1.0, 0.46, 0.35, 1.0];
+pub const HEART_COLOR: Color = [0.9, 0.1, 0.2, 1.0];
+pub const PATTERN_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
+pub const HUNT_PLAYER_COLORS: [Color; 2] = [[0.0, 0.35, 0.9, 1.0], [0.95, 0.55, 0.0, 1.0]];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum View {
+    // the squares as played
+    Board,
+    // every square covered, the board before the first click
+    Puzzle,
+    // every square uncovered, flags on mines stay
+    Solution,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RenderOptions {
+    // output pixels per window pixel at zoom 1
+    pub scale: u32,
+    pub palette: Palette,
+    pub high_contrast: bool,
+    pub patterns: bool,
+    pub view: View,
+    // flags on safe squares crossed out, and kept in the solution
+    pub misflags: bool,
+    // the clock as shown, the game's final time when left out
+    pub time_ms: Option<u64>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            scale: 1,
+            palette: palette::DEFAULT,
+            high_contrast: false,
+            patterns: false,
+            view: View::Board,
+            misflags: false,
+            time_ms: None,
+        }
+    }
+}
+
+// tenths of a second while playing, the exact millisecond once the game is over
+pub fn format_time(ms: u64, exact: bool) -> String {
+    let ms = min(ms, 9_999_999);
+    if exact {
+        format!("{:03}.{:03}", ms / 1000, ms % 1000)
+    } else {
+        format!("{:03}.{}", ms / 1000, ms % 1000 / 100)
+    }
+}
+
+// the built-in look, with the options' palette and high contrast over it
+struct Colors {
+    background: Color,
+    ui_rect: Color,
+    ui_text: Color,
+    cell_covered: Color,
+    cell_revealed: Color,
+    cell_border: Color,
+    mine_revealed: Color,
+    mine_border: Color,
+    numbers: [Color; 8],
+}
+
+impl Colors {
+    fn new(options: &RenderOptions) -> Colors {
+        let mut colors = Colors {
+            background: BACKGROUND_COLOR,
+            ui_rect: UI_RECT_COLOR,
+            ui_text: UI_TEXT_COLOR,
+            cell_covered: CELL_COVERED_COLOR,
+            cell_revealed: CELL_REVEALED_COLOR,
+            cell_border: CELL_BORDER_COLOR,
+            mine_revealed: options.palette.mine_revealed,
+            mine_border: options.palette.mine_border,
+            numbers: options.palette.numbers,
+        };
+
+        if options.high_contrast {
+            let theme = palette::get_high_contrast_theme();
+            colors.background = theme.background.unwrap_or(colors.background);
+            colors.ui_rect = theme.ui_rect.unwrap_or(colors.ui_rect);
+            colors.ui_text = theme.ui_text.unwrap_or(colors.ui_text);
+            colors.cell_covered = theme.cell_covered.unwrap_or(colors.cell_covered);
+            colors.cell_revealed = theme.cell_revealed.unwrap_or(colors.cell_revealed);
+            colors.cell_border = theme.cell_border.unwrap_or(colors.cell_border);
+        }
+
+        colors
+    }
+
+    fn get_number_color(&self, num: u32) -> Color {
+        match num {
+            1..=8 => self.numbers[num as usize - 1],
+            _ => palette::get_number_color(num),
+        }
+    }
+}
+
+fn invalid_data<E: ToString>(path: &Path, err: E) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), err.to_string()),
+    )
+}
+
+fn load_image(path: &Path) -> io::Result<RgbaImage> {
+    image::open(path)
+        .map(|x| x.to_rgba())
+        .map_err(|err| invalid_data(path, err))
+}
+
+// an image in memory, everything is laid out in window pixels and scaled on the way in
+struct Canvas {
+    image: RgbaImage,
+    scale: f64,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, scale: u32, background: Color) -> Canvas {
+        Canvas {
+            image: RgbaImage::from_pixel(width * scale, height * scale, to_rgba(background)),
+            scale: f64::from(scale),
+        }
+    }
+
+    // the output pixels from a to b, cut to the image
+    fn get_span(&self, a: f64, b: f64, size: u32) -> (u32, u32) {
+        let clamp = |x: f64| (x * self.scale).round().max(0.0).min(f64::from(size)) as u32;
+        (clamp(a), clamp(b))
+    }
+
+    fn blend(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+        let alpha = color[3] * coverage;
+        let pixel = self.image.get_pixel_mut(x, y);
+
+        for (dst, src) in pixel.data.iter_mut().zip(&color[..3]) {
+            let value = src * alpha + f32::from(*dst) / 255.0 * (1.0 - alpha);
+            *dst = (value * 255.0).round() as u8;
+        }
+        pixel.data[3] = 255;
+    }
+
+    // calls f with every output pixel whose center falls in the rectangle
+    fn for_each_pixel<F>(&mut self, rect: [f64; 4], mut f: F)
+    where
+        F: FnMut(&mut Canvas, u32, u32, f64, f64),
+    {
+        let (x0, x1) = self.get_span(rect[0], rect[0] + rect[2], self.image.width());
+        let (y0, y1) = self.get_span(rect[1], rect[1] + rect[3], self.image.height());
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let cx = (f64::from(x) + 0.5) / self.scale;
+                let cy = (f64::from(y) + 0.5) / self.scale;
+                f(self, x, y, cx, cy);
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, color: Color, rect: [f64; 4]) {
+        self.for_each_pixel(rect, |canvas, x, y, _, _| canvas.blend(x, y, color, 1.0));
+    }
+
+    // a border of 2 * radius centered on the rectangle's edge, as the window draws them
+    fn border_rect(&mut self, color: Color, rect: [f64; 4], radius: f64) {
+        let [x, y, w, h] = rect;
+        self.fill_rect(color, [x - radius, y - radius, w + radius * 2.0, radius * 2.0]);
+        self.fill_rect(color, [x - radius, y + h - radius, w + radius * 2.0, radius * 2.0]);
+        self.fill_rect(color, [x - radius, y + radius, radius * 2.0, h - radius * 2.0]);
+        self.fill_rect(color, [x + w - radius, y + radius, radius * 2.0, h - radius * 2.0]);
+    }
+
+    fn fill_ellipse(&mut self, color: Color, rect: [f64; 4]) {
+        let [x, y, w, h] = rect;
+        let (rx, ry) = (w * 0.5, h * 0.5);
+
+        self.for_each_pixel(rect, |canvas, px, py, cx, cy| {
+            let dx = (cx - x - rx) / rx;
+            let dy = (cy - y - ry) / ry;
+            if dx * dx + dy * dy <= 1.0 {
+                canvas.blend(px, py, color, 1.0);
+            }
+        });
+    }
+
+    fn fill_triangle(&mut self, color: Color, points: [[f64; 2]; 3]) {
+        let min_x = points.iter().map(|p| p[0]).fold(f64::MAX, f64::min);
+        let max_x = points.iter().map(|p| p[0]).fold(f64::MIN, f64::max);
+        let min_y = points.iter().map(|p| p[1]).fold(f64::MAX, f64::min);
+        let max_y = points.iter().map(|p| p[1]).fold(f64::MIN, f64::max);
+
+        let side = |a: [f64; 2], b: [f64; 2], x: f64, y: f64| {
+            (b[0] - a[0]) * (y - a[1]) - (b[1] - a[1]) * (x - a[0])
+        };
+
+        let rect = [min_x, min_y, max_x - min_x, max_y - min_y];
+        self.for_each_pixel(rect, |canvas, px, py, cx, cy| {
+            let sides = [
+                side(points[0], points[1], cx, cy),
+                side(points[1], points[2], cx, cy),
+                side(points[2], points[0], cx, cy),
+            ];
+            // inside whichever way the points wind
+            if sides.iter().all(|x| *x >= 0.0) || sides.iter().all(|x| *x <= 0.0) {
+                canvas.blend(px, py, color, 1.0);
+            }
+        });
+    }
+
+    fn line(&mut self, color: Color, radius: f64, line: [f64; 4]) {
+        let [x1, y1, x2, y2] = line;
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = dx * dx + dy * dy;
+
+        let rect = [
+            x1.min(x2) - radius,
+            y1.min(y2) - radius,
+            dx.abs() + radius * 2.0,
+            dy.abs() + radius * 2.0,
+        ];
+        self.for_each_pixel(rect, |canvas, px, py, cx, cy| {
+            let t = if length > 0.0 {
+                (((cx - x1) * dx + (cy - y1) * dy) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (ex, ey) = (x1 + t * dx - cx, y1 + t * dy - cy);
+            if ex * ex + ey * ey <= radius * radius {
+                canvas.blend(px, py, color, 1.0);
+            }
+        });
+    }
+
+    // stretched over the rectangle, sampling the nearest source pixel
+    fn draw_image(&mut self, image: &RgbaImage, rect: [f64; 4]) {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.for_each_pixel(rect, |canvas, px, py, cx, cy| {
+            let sx = ((cx - rect[0]) / rect[2] * f64::from(width)) as u32;
+            let sy = ((cy - rect[1]) / rect[3] * f64::from(height)) as u32;
+            let pixel = image.get_pixel(min(sx, width - 1), min(sy, height - 1));
+
+            let color = [
+                f32::from(pixel.data[0]) / 255.0,
+                f32::from(pixel.data[1]) / 255.0,
+                f32::from(pixel.data[2]) / 255.0,
+                1.0,
+            ];
+            canvas.blend(px, py, color, f32::from(pixel.data[3]) / 255.0);
+        });
+    }
+
+    // text at size / 2 window pixels starting on the baseline at x, y, as the window's zoomed
+    // down text
+    fn draw_text(&mut self, font: &Font, color: Color, size: u32, label: &str, x: f64, y: f64) {
+        let scale = Scale::uniform((f64::from(size) * 0.5 * self.scale) as f32);
+        let mut caret = x * self.scale;
+        let baseline = y * self.scale;
+
+        for ch in label.chars() {
+            let glyph = match font.glyph(ch) {
+                Some(glyph) => glyph.scaled(scale),
+                None => continue,
+            };
+            let advance = f64::from(glyph.h_metrics().advance_width);
+            let glyph = glyph.positioned(point(caret as f32, baseline as f32));
+
+            if let Some(bounds) = glyph.pixel_bounding_box() {
+                let (width, height) = self.image.dimensions();
+                glyph.draw(|gx, gy, coverage| {
+                    let px = bounds.min.x + gx as i32;
+                    let py = bounds.min.y + gy as i32;
+                    if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                        self.blend(px as u32, py as u32, color, coverage);
+                    }
+                });
+            }
+
+            caret += advance;
+        }
+    }
+}
+
+// what the options' view shows of a square
+fn get_shown_square(square: &Square, options: &RenderOptions) -> Square {
+    let mut square = *square;
+    match options.view {
+        View::Board => {}
+        View::Puzzle => {
+            square.state = SquareState::Covered;
+            square.exploded = false;
+            square.claimed_by = None;
+        }
+        View::Solution => {
+            let kept = square.is_mine || options.misflags;
+            if !(square.state == SquareState::Flagged && kept) {
+                square.state = SquareState::Revealed;
+            }
+        }
+    }
+    square
+}
+
+fn is_misflag(square: &Square) -> bool {
+    square.state == SquareState::Flagged && !square.is_mine
+}
+
+fn to_rgba(color: Color) -> Rgba<u8> {
+    Rgba {
+        data: [
+            (color[0] * 255.0).round() as u8,
+            (color[1] * 255.0).round() as u8,
+            (color[2] * 255.0).round() as u8,
+            (color[3] * 255.0).round() as u8,
+        ],
+    }
+}
+
+// width in window pixels of text drawn at size / 2
+fn get_text_width(font: &Font, size: u32, label: &str) -> f64 {
+    let scale = Scale::uniform(size as f32);
+    let width: f32 = label
+        .chars()
+        .filter_map(|x| font.glyph(x))
+        .map(|x| x.scaled(scale).h_metrics().advance_width)
+        .sum();
+
+    f64::from(width) * 0.5
+}
+
+// draws boards the way the window does without one, for screenshots and image tests
+pub struct Renderer {
+    font: Font<'static>,
+    mine: RgbaImage,
+    flag: RgbaImage,
+    win_face: RgbaImage,
+    ongoing_face: RgbaImage,
+    lost_face: RgbaImage,
+}
+
+impl Renderer {
+    // the font and icons the window uses out of the assets directory
+    pub fn load(assets: &Path) -> io::Result<Renderer> {
+        let path = assets.join("Andale-Mono.ttf");
+        let mut bytes = Vec::new();
+        File::open(&path)?.read_to_end(&mut bytes)?;
+        let font = FontCollection::from_bytes(bytes)
+            .into_font()
+            .ok_or_else(|| invalid_data(&path, "not a font"))?;
+
+        Ok(Renderer {
+            font,
+            mine: load_image(&assets.join("mine.png"))?,
+            flag: load_image(&assets.join("flag.png"))?,
+            win_face: load_image(&assets.join("cool.png"))?,
+            ongoing_face: load_image(&assets.join("happy.png"))?,
+            lost_face: load_image(&assets.join("shocked.png"))?,
+        })
+    }
+
+    // the board, its top bar and the end of game reveal as the window would show them at
+    // zoom 1, times the options' scale
+    pub fn render(&self, game: &MineSweeper, options: &RenderOptions) -> RgbaImage {
+        let colors = Colors::new(options);
+        let board_width = f64::from(game.cols * SQUARE_SIZE);
+
+        let mut canvas = Canvas::new(
+            game.cols * SQUARE_SIZE,
+            game.rows * SQUARE_SIZE + TOP_BAR_HEIGHT,
+            options.scale.max(1),
+            colors.background,
+        );
+
+        let flags_rect = [MARGIN, MARGIN, f64::from(UI_FONT_SIZE) * 1.15, UI_RECT_HEIGHT];
+        canvas.fill_rect(colors.ui_rect, flags_rect);
+
+        // mine hunt puts the scores where the flags and the time usually are
+        let scores = game.get_scores();
+        let (time_text, time_color) = if game.rules.mine_hunt {
+            (format!("{:03}", scores[1]), HUNT_PLAYER_COLORS[1])
+        } else {
+            (self.get_time_text(game, options), colors.ui_text)
+        };
+        let time_text_width = get_text_width(&self.font, TIME_FONT_SIZE, &time_text);
+        let time_rect_width = TIME_RECT_MIN_WIDTH.max(time_text_width + MARGIN * 4.0);
+        let time_rect = [
+            board_width - time_rect_width - MARGIN,
+            MARGIN,
+            time_rect_width,
+            UI_RECT_HEIGHT,
+        ];
+        canvas.fill_rect(colors.ui_rect, time_rect);
+
+        let highlighted = match game.state {
+            GameState::Ongoing => Some(game.get_turn()),
+            _ => game.get_winner(),
+        };
+        if let (true, Some(player)) = (game.rules.mine_hunt, highlighted) {
+            let rect = if player == 0 { flags_rect } else { time_rect };
+            canvas.border_rect(HUNT_PLAYER_COLORS[player], rect, HUNT_TURN_BORDER_RADIUS);
+        }
+
+        let face = match game.state {
+            GameState::Ongoing => &self.ongoing_face,
+            GameState::Won => &self.win_face,
+            GameState::Lost => &self.lost_face,
+        };
+        let (face_width, face_height) = face.dimensions();
+        let face_height = f64::from(face_height) * FACE_ICON_SIZE / f64::from(face_width.max(1));
+        let face_x = board_width * 0.5 - FACE_ICON_SIZE * 0.5;
+        let face_y = f64::from(TOP_BAR_HEIGHT) * 0.5 - face_height * 0.5;
+        Renderer::draw_raised(
+            &mut canvas,
+            &colors,
+            [
+                face_x - MARGIN,
+                face_y - MARGIN,
+                FACE_ICON_SIZE + MARGIN * 2.0,
+                face_height + MARGIN * 2.0,
+            ],
+        );
+
+        for i in 0..game.rows {
+            for j in 0..game.cols {
+                let x = f64::from(j * SQUARE_SIZE) + 2.0;
+                let y = f64::from(i * SQUARE_SIZE + TOP_BAR_HEIGHT) + 2.0;
+                let square = get_shown_square(game.get_square(i, j), options);
+
+                self.draw_square(&mut canvas, &colors, &square, x, y);
+                if options.misflags && is_misflag(&square) {
+                    Renderer::draw_misflag(&mut canvas, &colors, x, y);
+                }
+                if options.patterns {
+                    Renderer::draw_pattern(&mut canvas, &square, x, y);
+                }
+            }
+        }
+
+        let (flags_text, flags_color) = if game.rules.mine_hunt {
+            (format!("{:03}", scores[0]), HUNT_PLAYER_COLORS[0])
+        } else {
+            (format!("{:03}", game.get_flags_left()), colors.ui_text)
+        };
+        canvas.draw_text(&self.font, flags_color, UI_FONT_SIZE, &flags_text, 3.5, UI_FONT_Y_OFFSET);
+
+        if game.rules.lives > 1 {
+            let hearts_x = MARGIN + 1.0 + f64::from(UI_FONT_SIZE) * 1.15;
+            let hearts_y = f64::from(TOP_BAR_HEIGHT) * 0.5 - HEART_SIZE * 0.5;
+            self.draw_hearts(&mut canvas, game, hearts_x, hearts_y);
+        }
+
+        canvas.draw_image(face, [face_x, face_y, FACE_ICON_SIZE, face_height]);
+
+        // centered in the time box, which grows for the exact time at game end
+        canvas.draw_text(
+            &self.font,
+            time_color,
+            TIME_FONT_SIZE,
+            &time_text,
+            board_width - MARGIN - (time_rect_width + time_text_width) * 0.5,
+            UI_FONT_Y_OFFSET - 2.0,
+        );
+
+        canvas.image
+    }
+
+    pub fn save_png(
+        &self,
+        game: &MineSweeper,
+        options: &RenderOptions,
+        path: &Path,
+    ) -> io::Result<()> {
+        self.render(game, options)
+            .save(path)
+            .map_err(|err| invalid_data(path, err))
+    }
+
+    fn get_time_text(&self, game: &MineSweeper, options: &RenderOptions) -> String {
+        let exact = game.state != GameState::Ongoing;
+        let time_ms = options.time_ms.or_else(|| game.final_time_ms()).unwrap_or(0);
+
+        match (game.lose_reason(), game.rules.time_limit) {
+            (Some(LoseReason::TimeUp), _) => "TIME".to_owned(),
+            (_, Some(limit)) => format_time((limit * 1000).saturating_sub(time_ms), exact),
+            (_, None) => format_time(time_ms, exact),
+        }
+    }
+
+    fn draw_raised(canvas: &mut Canvas, colors: &Colors, rect: [f64; 4]) {
+        canvas.fill_rect(colors.cell_covered, rect);
+        canvas.border_rect(colors.cell_border, rect, 1.0);
+    }
+
+    fn draw_mine_cell(canvas: &mut Canvas, colors: &Colors, rect: [f64; 4]) {
+        canvas.fill_rect(colors.mine_border, rect);
+        canvas.border_rect(colors.mine_revealed, rect, 1.0);
+    }
+
+    fn draw_square(&self, canvas: &mut Canvas, colors: &Colors, square: &Square, x: f64, y: f64) {
+        let size = f64::from(SQUARE_SIZE);
+        let rect = [x, y, size - 4.0, size - 4.0];
+
+        match square.state {
+            SquareState::Covered => Renderer::draw_raised(canvas, colors, rect),
+            SquareState::Revealed => {
+                if let Some(player) = square.claimed_by {
+                    canvas.fill_rect(HUNT_PLAYER_COLORS[player], rect);
+                } else if square.is_mine {
+                    Renderer::draw_mine_cell(canvas, colors, rect);
+                } else {
+                    canvas.fill_rect(colors.cell_border, rect);
+                    canvas.border_rect(colors.cell_revealed, rect, 1.0);
+                }
+
+                if square.is_mine {
+                    let (width, height) = self.mine.dimensions();
+                    let mine_size = size * 0.06;
+                    let mine_height = f64::from(height) * MINE_ICON_SIZE / f64::from(width.max(1));
+                    canvas.draw_image(
+                        &self.mine,
+                        [x + mine_size, y + mine_size, MINE_ICON_SIZE, mine_height],
+                    );
+                } else if square.adjacent_mines > 0 {
+                    canvas.draw_text(
+                        &self.font,
+                        colors.get_number_color(square.adjacent_mines),
+                        NUMBER_FONT_SIZE,
+                        &square.adjacent_mines.to_string(),
+                        x + size * 0.19,
+                        y + size * 0.65,
+                    );
+                }
+            }
+            SquareState::Flagged => {
+                if square.exploded {
+                    Renderer::draw_mine_cell(canvas, colors, rect);
+                } else {
+                    Renderer::draw_raised(canvas, colors, rect);
+                }
+
+                let (width, height) = self.flag.dimensions();
+                let flag_size = size * 0.085;
+                let flag_height = f64::from(height) * FLAG_ICON_SIZE / f64::from(width.max(1));
+                canvas.draw_image(
+                    &self.flag,
+                    [x + flag_size, y + flag_size, FLAG_ICON_SIZE, flag_height],
+                );
+            }
+        }
+    }
+
+    fn draw_misflag(canvas: &mut Canvas, colors: &Colors, x: f64, y: f64) {
+        let size = f64::from(SQUARE_SIZE) - 4.0;
+        let (a, b) = (MISFLAG_INSET, size - MISFLAG_INSET);
+        canvas.line(colors.mine_revealed, MISFLAG_LINE_RADIUS, [x + a, y + a, x + b, y + b]);
+        canvas.line(colors.mine_revealed, MISFLAG_LINE_RADIUS, [x + b, y + a, x + a, y + b]);
+    }
+
+    // a cross over exploded mines and a notch in the corner of flags, as the window draws them
+    fn draw_pattern(canvas: &mut Canvas, square: &Square, x: f64, y: f64) {
+        let size = f64::from(SQUARE_SIZE) - 4.0;
+
+        if square.exploded {
+            canvas.line(PATTERN_COLOR, PATTERN_LINE_RADIUS, [x, y, x + size, y + size]);
+            canvas.line(PATTERN_COLOR, PATTERN_LINE_RADIUS, [x + size, y, x, y + size]);
+        } else if square.state == SquareState::Flagged {
+            canvas.fill_triangle(
+                PATTERN_COLOR,
+                [[x, y], [x + PATTERN_SIZE, y], [x, y + PATTERN_SIZE]],
+            );
+        }
+    }
+
+    fn draw_hearts(&self, canvas: &mut Canvas, game: &MineSweeper, x: f64, y: f64) {
+        let lobe = HEART_SIZE * 0.5;
+
+        canvas.fill_ellipse(HEART_COLOR, [x, y, lobe, lobe]);
+        canvas.fill_ellipse(HEART_COLOR, [x + lobe, y, lobe, lobe]);
+        canvas.fill_triangle(
+            HEART_COLOR,
+            [
+                [x + 0.2, y + lobe * 0.7],
+                [x + HEART_SIZE - 0.2, y + lobe * 0.7],
+                [x + lobe, y + HEART_SIZE],
+            ],
+        );
+
+        canvas.draw_text(
+            &self.font,
+            HEART_COLOR,
+            HEART_FONT_SIZE,
+            &game.get_lives_left().to_string(),
+            x + HEART_SIZE,
+            UI_FONT_Y_OFFSET - 2.0,
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use game::{MineSweeper, Square, SquareState};
+use palette::Color;
+
+use super::{
+    get_shown_square, is_misflag, Colors, RenderOptions, HUNT_PLAYER_COLORS, MISFLAG_INSET,
+    MISFLAG_LINE_RADIUS, NUMBER_FONT_SIZE, PATTERN_COLOR, PATTERN_LINE_RADIUS, PATTERN_SIZE,
+    SQUARE_SIZE,
+};
+
+// the window uses images for these, which do not scale
+const MINE_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
+const FLAG_COLOR: Color = [0.9, 0.1, 0.1, 1.0];
+const NUMBER_ATTRIBUTES: &str = r#"font-family="Andale Mono, monospace" text-anchor="middle""#;
+
+// hex, every color drawn is opaque
+fn get_svg_color(color: Color) -> String {
+    let channel = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2])
+    )
+}
+
+// the board without its top bar, in window pixels at zoom 1 times the options' scale, the
+// numbers as text and the mines and flags as shapes so it prints sharp at any size
+pub fn render_svg(game: &MineSweeper, options: &RenderOptions) -> String {
+    let colors = Colors::new(options);
+    let width = game.cols * SQUARE_SIZE;
+    let height = game.rows * SQUARE_SIZE;
+    let scale = options.scale.max(1);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        width * scale,
+        height * scale,
+        width,
+        height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="{}" height="{}" fill="{}"/>"#,
+        width,
+        height,
+        get_svg_color(colors.background)
+    )
+    .unwrap();
+
+    for i in 0..game.rows {
+        for j in 0..game.cols {
+            let x = f64::from(j * SQUARE_SIZE) + 2.0;
+            let y = f64::from(i * SQUARE_SIZE) + 2.0;
+            let square = get_shown_square(game.get_square(i, j), options);
+
+            write_square(&mut svg, &colors, &square, x, y);
+            if options.misflags && is_misflag(&square) {
+                let color = colors.mine_revealed;
+                write_cross(&mut svg, color, MISFLAG_LINE_RADIUS, MISFLAG_INSET, x, y);
+            }
+            if options.patterns {
+                write_pattern(&mut svg, &square, x, y);
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn save_svg(game: &MineSweeper, options: &RenderOptions, path: &Path) -> io::Result<()> {
+    File::create(path)?.write_all(render_svg(game, options).as_bytes())
+}
+
+// the border straddles the square's edge like the window's
+fn write_rect(svg: &mut String, fill: Color, border: Color, x: f64, y: f64) {
+    let size = f64::from(SQUARE_SIZE) - 4.0;
+    writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="{}" stroke-width="2"/>"#,
+        x,
+        y,
+        size,
+        size,
+        get_svg_color(fill),
+        get_svg_color(border)
+    )
+    .unwrap();
+}
+
+fn write_line(svg: &mut String, color: Color, radius: f64, line: [f64; 4]) {
+    writeln!(
+        svg,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+        line[0],
+        line[1],
+        line[2],
+        line[3],
+        get_svg_color(color),
+        radius * 2.0
+    )
+    .unwrap();
+}
+
+fn write_cross(svg: &mut String, color: Color, radius: f64, inset: f64, x: f64, y: f64) {
+    let (a, b) = (inset, f64::from(SQUARE_SIZE) - 4.0 - inset);
+    write_line(svg, color, radius, [x + a, y + a, x + b, y + b]);
+    write_line(svg, color, radius, [x + b, y + a, x + a, y + b]);
+}
+
+fn write_square(svg: &mut String, colors: &Colors, square: &Square, x: f64, y: f64) {
+    match square.state {
+        SquareState::Covered => write_rect(svg, colors.cell_covered, colors.cell_border, x, y),
+        SquareState::Revealed => {
+            if let Some(player) = square.claimed_by {
+                let color = HUNT_PLAYER_COLORS[player];
+                write_rect(svg, color, color, x, y);
+            } else if square.is_mine {
+                write_rect(svg, colors.mine_border, colors.mine_revealed, x, y);
+            } else {
+                write_rect(svg, colors.cell_border, colors.cell_revealed, x, y);
+            }
+
+            if square.is_mine {
+                write_mine(svg, x, y);
+            } else if square.adjacent_mines > 0 {
+                // the window draws numbers at half their font size
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" fill="{}" font-size="{}" {}>{}</text>"#,
+                    x + 8.0,
+                    y + 13.0,
+                    get_svg_color(colors.get_number_color(square.adjacent_mines)),
+                    f64::from(NUMBER_FONT_SIZE) * 0.5,
+                    NUMBER_ATTRIBUTES,
+                    square.adjacent_mines
+                )
+                .unwrap();
+            }
+        }
+        SquareState::Flagged => {
+            if square.exploded {
+                write_rect(svg, colors.mine_border, colors.mine_revealed, x, y);
+            } else {
+                write_rect(svg, colors.cell_covered, colors.cell_border, x, y);
+            }
+            write_flag(svg, x, y);
+        }
+    }
+}
+
+// a round mine with four spikes
+fn write_mine(svg: &mut String, x: f64, y: f64) {
+    let (cx, cy) = (x + 8.0, y + 8.0);
+    write_line(svg, MINE_COLOR, 0.75, [cx - 6.0, cy, cx + 6.0, cy]);
+    write_line(svg, MINE_COLOR, 0.75, [cx, cy - 6.0, cx, cy + 6.0]);
+    writeln!(
+        svg,
+        r#"<circle cx="{}" cy="{}" r="4.5" fill="{}"/>"#,
+        cx,
+        cy,
+        get_svg_color(MINE_COLOR)
+    )
+    .unwrap();
+}
+
+// a pennant on a pole standing on a base
+fn write_flag(svg: &mut String, x: f64, y: f64) {
+    write_line(svg, MINE_COLOR, 0.5, [x + 6.0, y + 3.0, x + 6.0, y + 13.0]);
+    write_line(svg, MINE_COLOR, 0.75, [x + 3.5, y + 13.0, x + 10.5, y + 13.0]);
+    writeln!(
+        svg,
+        r#"<polygon points="{},{} {},{} {},{}" fill="{}"/>"#,
+        x + 6.5,
+        y + 2.5,
+        x + 13.0,
+        y + 5.25,
+        x + 6.5,
+        y + 8.0,
+        get_svg_color(FLAG_COLOR)
+    )
+    .unwrap();
+}
+
+// the same shapes the window draws with patterns on
+fn write_pattern(svg: &mut String, square: &Square, x: f64, y: f64) {
+    if square.exploded {
+        write_cross(svg, PATTERN_COLOR, PATTERN_LINE_RADIUS, 0.0, x, y);
+    } else if square.state == SquareState::Flagged {
+        writeln!(
+            svg,
+            r#"<polygon points="{},{} {},{} {},{}" fill="{}"/>"#,
+            x,
+            y,
+            x + PATTERN_SIZE,
+            y,
+            x,
+            y + PATTERN_SIZE,
+            get_svg_color(PATTERN_COLOR)
+        )
+        .unwrap();
+    }
+}
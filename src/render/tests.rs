@@ -0,0 +1,129 @@
+use super::*;
+
+use std::env;
+
+use game::Rules;
+
+fn get_renderer() -> Renderer {
+    Renderer::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")).unwrap()
+}
+
+// the middle of the square at row i and column j, at scale 1
+fn get_center(i: u32, j: u32) -> (u32, u32) {
+    (j * SQUARE_SIZE + 10, i * SQUARE_SIZE + TOP_BAR_HEIGHT + 10)
+}
+
+#[test]
+fn test_render() {
+    let renderer = get_renderer();
+    let game = MineSweeper::from_layout("##*#\nF2##\n.1M#", Rules::default()).unwrap();
+
+    let image = renderer.render(&game, &RenderOptions::default());
+    assert_eq!(image.dimensions(), (80, 3 * SQUARE_SIZE + TOP_BAR_HEIGHT));
+    assert_eq!(*image.get_pixel(0, 0), to_rgba(BACKGROUND_COLOR));
+
+    let (x, y) = get_center(0, 0);
+    assert_eq!(*image.get_pixel(x, y), to_rgba(CELL_COVERED_COLOR));
+    let (x, y) = get_center(2, 0);
+    assert_eq!(*image.get_pixel(x, y), to_rgba(CELL_BORDER_COLOR));
+
+    // the solution uncovers the safe squares
+    let options = RenderOptions {
+        view: View::Solution,
+        ..RenderOptions::default()
+    };
+    let revealed = renderer.render(&game, &options);
+    let (x, y) = get_center(0, 0);
+    assert_eq!(*revealed.get_pixel(x, y), to_rgba(CELL_BORDER_COLOR));
+    assert!(*revealed != *image);
+
+    let options = RenderOptions {
+        view: View::Puzzle,
+        ..RenderOptions::default()
+    };
+    let puzzle = renderer.render(&game, &options);
+    let (x, y) = get_center(2, 0);
+    assert_eq!(*puzzle.get_pixel(x, y), to_rgba(CELL_COVERED_COLOR));
+
+    let options = RenderOptions {
+        scale: 2,
+        ..RenderOptions::default()
+    };
+    let scaled = renderer.render(&game, &options);
+    assert_eq!(scaled.dimensions(), (160, 2 * (3 * SQUARE_SIZE + TOP_BAR_HEIGHT)));
+    let (x, y) = get_center(2, 0);
+    assert_eq!(*scaled.get_pixel(x * 2, y * 2), to_rgba(CELL_BORDER_COLOR));
+
+    let options = RenderOptions {
+        high_contrast: true,
+        ..RenderOptions::default()
+    };
+    let high_contrast = renderer.render(&game, &options);
+    assert!(*high_contrast.get_pixel(0, 0) != to_rgba(BACKGROUND_COLOR));
+}
+
+#[test]
+fn test_format_time() {
+    assert_eq!(format_time(12_345, false), "012.3");
+    assert_eq!(format_time(12_345, true), "012.345");
+    assert_eq!(format_time(100_000_000, true), "9999.999");
+}
+
+#[test]
+fn test_shown_square() {
+    let game = MineSweeper::from_layout("F1\n#*", Rules::default()).unwrap();
+    let misflag = game.get_square(0, 0);
+    let mine = game.get_square(1, 1);
+
+    let mut options = RenderOptions::default();
+    assert_eq!(get_shown_square(misflag, &options).state, SquareState::Flagged);
+    assert!(is_misflag(misflag));
+
+    options.view = View::Solution;
+    assert_eq!(get_shown_square(misflag, &options).state, SquareState::Revealed);
+    assert_eq!(get_shown_square(mine, &options).state, SquareState::Revealed);
+    options.misflags = true;
+    assert_eq!(get_shown_square(misflag, &options).state, SquareState::Flagged);
+
+    options.view = View::Puzzle;
+    assert_eq!(get_shown_square(game.get_square(0, 1), &options).state, SquareState::Covered);
+}
+
+#[test]
+fn test_svg() {
+    let game = MineSweeper::from_layout("##*#\nF2##\n.1M#", Rules::default()).unwrap();
+
+    let board = svg::render_svg(&game, &RenderOptions::default());
+    assert!(board.starts_with("<svg "));
+    assert!(board.contains(r#"width="80" height="60" viewBox="0 0 80 60""#));
+    assert!(board.ends_with("</svg>\n"));
+    assert_eq!(board.matches("<text ").count(), 2);
+    // the numbers in the window's colors, and a flag for each flagged square
+    assert!(board.contains(r##"fill="#0000ff" font-size="11.5""##));
+    assert_eq!(board.matches("<polygon ").count(), 2);
+
+    let options = RenderOptions {
+        scale: 3,
+        misflags: true,
+        ..RenderOptions::default()
+    };
+    let misflags = svg::render_svg(&game, &options);
+    assert!(misflags.contains(r#"width="240" height="180""#));
+    assert_eq!(misflags.matches("<line ").count(), board.matches("<line ").count() + 2);
+
+    let options = RenderOptions {
+        view: View::Puzzle,
+        ..RenderOptions::default()
+    };
+    let puzzle = svg::render_svg(&game, &options);
+    assert_eq!(puzzle.matches("<text ").count(), 0);
+    assert_eq!(puzzle.matches("<rect ").count(), 13);
+
+    let options = RenderOptions {
+        view: View::Solution,
+        ..RenderOptions::default()
+    };
+    let solution = svg::render_svg(&game, &options);
+    assert_eq!(solution.matches("<circle ").count(), 1);
+    assert_eq!(solution.matches("<text ").count(), 7);
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use dirs;
+
+use game::preset::Preset;
+use game::{MineSweeper, PauseInterval};
+
+pub const MAX_ENTRIES: usize = 10;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScoreEntry {
+    pub name: String,
+    // local date of the win, YYYY-MM-DD
+    pub date: String,
+    pub time_ms: u64,
+    pub bbbv_per_sec: f64,
+    pub seed: u64,
+    // hints shown during the game, which the scores mark
+    pub hints: u32,
+    // when the game was paused and for how long, kept out of the time
+    pub pauses: Vec<PauseInterval>,
+}
+
+impl ScoreEntry {
+    // the entry for a game just won today
+    pub fn new(name: &str, game: &MineSweeper) -> ScoreEntry {
+        let time_ms = game.final_time_ms().unwrap_or(0);
+
+        ScoreEntry {
+            // tabs and line breaks would split the entry in the file
+            name: name.trim().replace(|x: char| x.is_control(), " "),
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            time_ms,
+            bbbv_per_sec: f64::from(game.get_3bv()) * 1000.0 / time_ms.max(1) as f64,
+            seed: game.get_seed(),
+            hints: game.get_hint_count(),
+            pauses: game.pause_intervals().to_vec(),
+        }
+    }
+}
+
+// boards are kept apart by their dimensions and mine count as well as the preset name, so the
+// same name in another preset set gets its own board, anything not played on its preset is custom
+pub fn get_board_key(preset: &Preset, game: &MineSweeper) -> String {
+    let on_preset =
+        (preset.cols, preset.rows, preset.mines) == (game.cols, game.rows, game.num_mines);
+    let name = if on_preset { &preset.name[..] } else { "Custom" };
+
+    format!("{} {}x{} {}", name, game.cols, game.rows, game.num_mines)
+}
+
+fn get_pauses_text(pauses: &[PauseInterval]) -> String {
+    pauses
+        .iter()
+        .map(|x| format!("{}+{}", x.at_ms, x.duration_ms))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_pauses(text: &str) -> Option<Vec<PauseInterval>> {
+    if text.is_empty() {
+        return Some(Vec::new());
+    }
+
+    text.split(',')
+        .map(|x| {
+            let mut parts = x.splitn(2, '+');
+            match (parts.next()?.parse(), parts.next()?.parse()) {
+                (Ok(at_ms), Ok(duration_ms)) => Some(PauseInterval { at_ms, duration_ms }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn get_default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("minesweep-rs").join("highscores.txt"))
+}
+
+// one line per entry, tab separated with the name last
+//
+//   <board key>\t<time ms>\t<3BV/s>\t<seed>\t<date>\t<hints>\t<pauses>\t<name>
+//
+// pauses are <at ms>+<duration ms> joined by commas, files from before pauses have no pauses
+// field and files from before hints have neither
+#[derive(Default)]
+pub struct HighScores {
+    boards: BTreeMap<String, Vec<ScoreEntry>>,
+}
+
+impl HighScores {
+    // a missing file is an empty leaderboard, unreadable lines are skipped
+    pub fn load(path: &Path) -> io::Result<HighScores> {
+        let mut scores = HighScores::default();
+
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(scores),
+            Err(err) => return Err(err),
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let fields: Vec<_> = line.splitn(8, '\t').collect();
+            let (hints, pauses, name) = match fields.len() {
+                6 => (Ok(0), Some(Vec::new()), fields[5]),
+                7 => (fields[5].parse(), Some(Vec::new()), fields[6]),
+                8 => (fields[5].parse(), parse_pauses(fields[6]), fields[7]),
+                _ => continue,
+            };
+
+            if let (Ok(time_ms), Ok(bbbv_per_sec), Ok(seed), Ok(hints), Some(pauses)) = (
+                fields[1].parse(),
+                fields[2].parse(),
+                fields[3].parse(),
+                hints,
+                pauses,
+            ) {
+                scores.add(
+                    fields[0],
+                    ScoreEntry {
+                        name: name.to_owned(),
+                        date: fields[4].to_owned(),
+                        time_ms,
+                        bbbv_per_sec,
+                        seed,
+                        hints,
+                        pauses,
+                    },
+                );
+            }
+        }
+
+        Ok(scores)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = File::create(path)?;
+        for (key, entries) in &self.boards {
+            for entry in entries {
+                writeln!(
+                    file,
+                    "{}\t{}\t{:.3}\t{}\t{}\t{}\t{}\t{}",
+                    key,
+                    entry.time_ms,
+                    entry.bbbv_per_sec,
+                    entry.seed,
+                    entry.date,
+                    entry.hints,
+                    get_pauses_text(&entry.pauses),
+                    entry.name
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_entries(&self, key: &str) -> &[ScoreEntry] {
+        self.boards.get(key).map_or(&[], |entries| &entries[..])
+    }
+
+    // whether the time would make it onto the board
+    pub fn qualifies(&self, key: &str, time_ms: u64) -> bool {
+        let entries = self.get_entries(key);
+        entries.len() < MAX_ENTRIES || entries.iter().any(|x| time_ms < x.time_ms)
+    }
+
+    // the entry's place on the board starting from 0, None if it was too slow
+    pub fn add(&mut self, key: &str, entry: ScoreEntry) -> Option<usize> {
+        let entries = self.boards.entry(key.to_owned()).or_default();
+        // ties go to whoever got there first
+        let rank = entries
+            .iter()
+            .position(|x| entry.time_ms < x.time_ms)
+            .unwrap_or(entries.len());
+
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+
+        entries.insert(rank, entry);
+        entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+use std::env;
+
+use game::clock::ManualClock;
+use game::{GameState, Position, Rules};
+
+fn get_entry(name: &str, time_ms: u64) -> ScoreEntry {
+    ScoreEntry {
+        name: name.to_owned(),
+        date: "2018-06-01".to_owned(),
+        time_ms,
+        bbbv_per_sec: 1.5,
+        seed: 42,
+        hints: 0,
+        pauses: Vec::new(),
+    }
+}
+
+#[test]
+fn test_board_key() {
+    let game = MineSweeper::new(9, 7, 5);
+
+    let expert = Preset::new("Expert", 30, 16, 99);
+
+    assert_eq!(get_board_key(&Preset::new("Tiny", 9, 7, 5), &game), "Tiny 9x7 5");
+    assert_eq!(get_board_key(&expert, &game), "Custom 9x7 5");
+    assert_eq!(
+        get_board_key(&expert, &MineSweeper::new(30, 16, 99)),
+        "Expert 30x16 99"
+    );
+}
+
+#[test]
+fn test_add() {
+    let mut scores = HighScores::default();
+
+    for i in 0..MAX_ENTRIES as u64 {
+        assert!(scores.qualifies("Beginner", 5000));
+        assert_eq!(scores.add("Beginner", get_entry("slow", 2000 + i)), Some(i as usize));
+    }
+
+    // a full board only takes times beating its last entry, and ties keep the older one
+    assert!(!scores.qualifies("Beginner", 2009));
+    assert_eq!(scores.add("Beginner", get_entry("late", 2009)), None);
+    assert_eq!(scores.add("Beginner", get_entry("fast", 2001)), Some(2));
+
+    let entries = scores.get_entries("Beginner");
+    assert_eq!(entries.len(), MAX_ENTRIES);
+    assert_eq!(entries[2].name, "fast");
+    assert_eq!(entries[MAX_ENTRIES - 1].time_ms, 2008);
+
+    assert!(scores.get_entries("Expert").is_empty());
+    assert!(scores.qualifies("Expert", 999_999));
+}
+
+#[test]
+fn test_save_load() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-scores-{}", ::std::process::id()))
+        .join("highscores.txt");
+
+    assert!(HighScores::load(&path).unwrap().get_entries("Expert").is_empty());
+
+    let mut scores = HighScores::default();
+    scores.add("Expert", get_entry("tab\tin name", 123_456));
+    scores.add(
+        "Custom 30x16 99",
+        ScoreEntry {
+            hints: 2,
+            pauses: vec![
+                PauseInterval {
+                    at_ms: 2_000,
+                    duration_ms: 60_000,
+                },
+                PauseInterval {
+                    at_ms: 45_500,
+                    duration_ms: 250,
+                },
+            ],
+            ..get_entry("custom", 99_000)
+        },
+    );
+    scores.save(&path).unwrap();
+
+    let loaded = HighScores::load(&path).unwrap();
+    assert_eq!(loaded.get_entries("Expert"), scores.get_entries("Expert"));
+    assert_eq!(
+        loaded.get_entries("Custom 30x16 99"),
+        scores.get_entries("Custom 30x16 99")
+    );
+
+    // lines from before pauses were recorded
+    File::create(&path)
+        .unwrap()
+        .write_all(b"Expert\t123456\t1.500\t42\t2018-06-01\t0\told\n")
+        .unwrap();
+    assert_eq!(
+        HighScores::load(&path).unwrap().get_entries("Expert"),
+        &[get_entry("old", 123_456)][..]
+    );
+
+    // lines from before hints were counted
+    File::create(&path)
+        .unwrap()
+        .write_all(b"Expert\t123456\t1.500\t42\t2018-06-01\told\n")
+        .unwrap();
+    assert_eq!(
+        HighScores::load(&path).unwrap().get_entries("Expert"),
+        &[get_entry("old", 123_456)][..]
+    );
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_save_load_ms() {
+    let path = env::temp_dir()
+        .join(format!("minesweep-rs-scores-ms-{}", ::std::process::id()))
+        .join("highscores.txt");
+
+    let mut game = MineSweeper::from_layout("*#\n##", Rules::default()).unwrap();
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+
+    game.reveal_square(&Position(1, 1));
+    game.first_moved();
+    clock.advance(1_234);
+    game.toggle_flag_square(&Position(0, 0));
+    game.update_game_state();
+    assert_eq!(game.state, GameState::Won);
+
+    let mut scores = HighScores::default();
+    scores.add("Custom 2x2 1", ScoreEntry::new("quick", &game));
+    scores.save(&path).unwrap();
+
+    let loaded = HighScores::load(&path).unwrap();
+    assert_eq!(loaded.get_entries("Custom 2x2 1")[0].time_ms, 1_234);
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
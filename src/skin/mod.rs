@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use toml;
+
+use config::ThemeConfig;
+
+pub const MANIFEST_NAME: &str = "skin.toml";
+
+// skin.toml in the skin's directory, file names are relative to it
+//
+//   name = "Classic"
+//   bevel = true
+//   font = "font.ttf"
+//
+//   [theme]
+//   background = [0.75, 0.75, 0.75, 1.0]
+//   numbers = [[0.0, 0.0, 1.0, 1.0], [0.0, 0.5, 0.0, 1.0]]
+//
+//   [textures]
+//   mine = "mine.png"
+//
+// textures are mine, flag, win_face, ongoing_face, lost_face, cell_covered and cell_revealed,
+// anything left out falls back to the built-in look
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkinManifest {
+    pub name: String,
+    // covered cells drawn raised with light and shadow edges instead of flat
+    pub bevel: bool,
+    pub font: Option<String>,
+    pub theme: ThemeConfig,
+    pub textures: BTreeMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Skin {
+    // None for the built-in skin
+    pub dir: Option<PathBuf>,
+    pub manifest: SkinManifest,
+}
+
+fn invalid_data<E: ToString>(path: &Path, err: E) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), err.to_string()),
+    )
+}
+
+impl Skin {
+    pub fn builtin() -> Skin {
+        Skin {
+            dir: None,
+            manifest: SkinManifest {
+                name: "Default".to_owned(),
+                ..SkinManifest::default()
+            },
+        }
+    }
+
+    // a manifest without a name goes by its directory's
+    pub fn load(dir: &Path) -> io::Result<Skin> {
+        let path = dir.join(MANIFEST_NAME);
+        let mut contents = String::new();
+        File::open(&path)?.read_to_string(&mut contents)?;
+
+        let mut manifest: SkinManifest =
+            toml::from_str(&contents).map_err(|err| invalid_data(&path, err))?;
+        if manifest.name.is_empty() {
+            manifest.name = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+
+        Ok(Skin {
+            dir: Some(dir.to_owned()),
+            manifest,
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    // where the skin keeps a texture, None when it leaves it to the built-in one
+    pub fn get_texture(&self, name: &str) -> Option<PathBuf> {
+        self.get_file(self.manifest.textures.get(name))
+    }
+
+    pub fn get_font(&self) -> Option<PathBuf> {
+        self.get_file(self.manifest.font.as_ref())
+    }
+
+    fn get_file(&self, file: Option<&String>) -> Option<PathBuf> {
+        match (&self.dir, file) {
+            (Some(dir), Some(file)) => Some(dir.join(file)),
+            _ => None,
+        }
+    }
+}
+
+// every directory under dir with a manifest, sorted by name, a missing dir has none
+pub fn find_skins(dir: &Path) -> Vec<io::Result<Skin>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut dirs: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_NAME).is_file())
+        .collect();
+    dirs.sort();
+
+    dirs.iter().map(|dir| Skin::load(dir)).collect()
+}
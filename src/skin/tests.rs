@@ -0,0 +1,69 @@
+use super::*;
+
+use std::env;
+use std::io::Write;
+
+fn write_manifest(dir: &Path, contents: &str) {
+    fs::create_dir_all(dir).unwrap();
+    File::create(dir.join(MANIFEST_NAME))
+        .unwrap()
+        .write_all(contents.as_bytes())
+        .unwrap();
+}
+
+#[test]
+fn test_load() {
+    let root = env::temp_dir().join(format!("minesweep-rs-skins-{}", ::std::process::id()));
+
+    write_manifest(
+        &root.join("retro"),
+        r#"
+name = "Retro"
+bevel = true
+
+[theme]
+background = [0.75, 0.75, 0.75, 1.0]
+numbers = [[0.0, 0.0, 1.0, 1.0]]
+
+[textures]
+mine = "bomb.png"
+"#,
+    );
+    write_manifest(&root.join("plain"), "");
+    write_manifest(&root.join("broken"), "bevel = \"yes\"");
+    // not a skin without a manifest
+    fs::create_dir_all(root.join("empty")).unwrap();
+
+    let skins = find_skins(&root);
+    assert_eq!(skins.len(), 3);
+    assert!(skins[0].is_err());
+
+    let plain = skins[1].as_ref().unwrap();
+    assert_eq!(plain.get_name(), "plain");
+    assert!(!plain.manifest.bevel);
+    assert_eq!(plain.get_texture("mine"), None);
+
+    let retro = skins[2].as_ref().unwrap();
+    assert_eq!(retro.get_name(), "Retro");
+    assert!(retro.manifest.bevel);
+    assert_eq!(retro.manifest.theme.numbers, Some(vec![[0.0, 0.0, 1.0, 1.0]]));
+    assert_eq!(retro.manifest.theme.ui_text, None);
+    assert_eq!(retro.get_texture("mine"), Some(root.join("retro").join("bomb.png")));
+    assert_eq!(retro.get_font(), None);
+
+    assert_eq!(Skin::builtin().get_texture("mine"), None);
+    assert!(find_skins(&root.join("missing")).is_empty());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_shipped_skins() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join("skins");
+    let skins: Vec<_> = find_skins(&dir).into_iter().map(|x| x.unwrap()).collect();
+
+    let names: Vec<_> = skins.iter().map(|x| x.get_name()).collect();
+    assert_eq!(names, vec!["Classic", "Dark"]);
+    assert!(skins[0].manifest.bevel);
+    assert_eq!(skins[0].manifest.theme.numbers.as_ref().map(|x| x.len()), Some(8));
+}
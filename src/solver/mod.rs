@@ -0,0 +1,569 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use game::{Position, VisibleSquare};
+
+// convention [row][col], as returned by MineSweeper::get_visible_board
+pub type Board = [Vec<VisibleSquare>];
+
+// components bigger than this are not worth enumerating, their squares are treated
+// like the unconstrained ones when estimating and nothing is deduced from them
+const MAX_ENUMERATION_STEPS: u32 = 200_000;
+const EPSILON: f64 = 1e-9;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Reason {
+    // the number at the position on its own
+    Single(Position),
+    // the squares shared by the numbers at the two positions
+    Pair(Position, Position),
+    // all covered squares are accounted for by the number of mines left
+    MineCount,
+    // holds for every arrangement of mines consistent with the numbers
+    Enumeration,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Deduction {
+    pub pos: Position,
+    pub is_mine: bool,
+    pub reason: Reason,
+}
+
+// a revealed number and the covered squares around it still hiding `mines` mines
+#[derive(Clone, Debug)]
+struct Constraint {
+    source: Position,
+    cells: Vec<Position>,
+    mines: u32,
+}
+
+// mine arrangements of a group of constraints sharing squares, indexed by mine count
+struct Component {
+    cells: Vec<Position>,
+    solutions: Vec<f64>,
+    cell_solutions: Vec<Vec<f64>>,
+}
+
+pub fn get_neighbors(board: &Board, pos: &Position) -> Vec<Position> {
+    let rows = board.len() as i64;
+    let cols = board.first().map_or(0, |row| row.len()) as i64;
+    let mut neighbors = Vec::new();
+
+    for d_row in -1..2 {
+        for d_col in -1..2 {
+            let row = i64::from(pos.0) + d_row;
+            let col = i64::from(pos.1) + d_col;
+
+            if (d_row != 0 || d_col != 0) && row >= 0 && col >= 0 && row < rows && col < cols {
+                neighbors.push(Position(row as u32, col as u32));
+            }
+        }
+    }
+
+    neighbors
+}
+
+fn get(board: &Board, pos: &Position) -> VisibleSquare {
+    board[pos.0 as usize][pos.1 as usize]
+}
+
+fn is_known_mine(square: VisibleSquare) -> bool {
+    matches!(
+        square,
+        VisibleSquare::Flagged | VisibleSquare::Mine | VisibleSquare::Exploded
+    )
+}
+
+fn get_positions(board: &Board) -> Vec<Position> {
+    let mut positions = Vec::new();
+    for (i, row) in board.iter().enumerate() {
+        for j in 0..row.len() {
+            positions.push(Position(i as u32, j as u32));
+        }
+    }
+
+    positions
+}
+
+fn get_covered(board: &Board) -> Vec<Position> {
+    get_positions(board)
+        .into_iter()
+        .filter(|pos| get(board, pos) == VisibleSquare::Covered)
+        .collect()
+}
+
+fn get_mines_left(board: &Board, num_mines: u32) -> u32 {
+    let known = get_positions(board)
+        .iter()
+        .filter(|pos| is_known_mine(get(board, pos)))
+        .count() as u32;
+
+    num_mines.saturating_sub(known)
+}
+
+fn get_constraints(board: &Board) -> Vec<Constraint> {
+    let mut constraints = Vec::new();
+
+    for pos in get_positions(board) {
+        if let VisibleSquare::Revealed(num) = get(board, &pos) {
+            let neighbors = get_neighbors(board, &pos);
+            let known = neighbors
+                .iter()
+                .filter(|n| is_known_mine(get(board, n)))
+                .count() as u32;
+            let cells: Vec<_> = neighbors
+                .into_iter()
+                .filter(|n| get(board, n) == VisibleSquare::Covered)
+                .collect();
+
+            // a wrongly placed flag can leave a number with more mines than it shows
+            if !cells.is_empty() && known <= num {
+                constraints.push(Constraint {
+                    source: pos,
+                    cells,
+                    mines: num - known,
+                });
+            }
+        }
+    }
+
+    constraints
+}
+
+fn push_all(
+    deductions: &mut Vec<Deduction>,
+    seen: &mut HashSet<Position>,
+    cells: &[Position],
+    is_mine: bool,
+    reason: &Reason,
+) {
+    for pos in cells {
+        if seen.insert(*pos) {
+            deductions.push(Deduction {
+                pos: *pos,
+                is_mine,
+                reason: reason.clone(),
+            });
+        }
+    }
+}
+
+fn find_single_deductions(constraints: &[Constraint]) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    let mut seen = HashSet::new();
+
+    for c in constraints {
+        let reason = Reason::Single(c.source);
+        if c.mines == 0 {
+            push_all(&mut deductions, &mut seen, &c.cells, false, &reason);
+        } else if c.mines as usize == c.cells.len() {
+            push_all(&mut deductions, &mut seen, &c.cells, true, &reason);
+        }
+    }
+
+    deductions
+}
+
+fn find_pair_deductions(constraints: &[Constraint]) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    let mut seen = HashSet::new();
+
+    for a in constraints {
+        for b in constraints {
+            if a.source == b.source {
+                continue;
+            }
+
+            let shared = a.cells.iter().filter(|x| b.cells.contains(x)).count() as u32;
+            if shared == 0 {
+                continue;
+            }
+
+            let a_only = a.cells.len() as u32 - shared;
+            let b_only: Vec<_> = b
+                .cells
+                .iter()
+                .cloned()
+                .filter(|x| !a.cells.contains(x))
+                .collect();
+            let b_only_len = b_only.len() as u32;
+
+            if b_only.is_empty() {
+                continue;
+            }
+
+            // bounds on the mines a and b share, then whatever b has left goes outside of a
+            let max_shared = *[shared, a.mines, b.mines].iter().min().unwrap();
+            let min_shared = *[
+                0,
+                a.mines.saturating_sub(a_only),
+                b.mines.saturating_sub(b_only_len),
+            ].iter()
+                .max()
+                .unwrap();
+
+            let reason = Reason::Pair(a.source, b.source);
+            if min_shared <= max_shared && b.mines == min_shared {
+                push_all(&mut deductions, &mut seen, &b_only, false, &reason);
+            } else if min_shared <= max_shared && b.mines - max_shared == b_only_len {
+                push_all(&mut deductions, &mut seen, &b_only, true, &reason);
+            }
+        }
+    }
+
+    deductions
+}
+
+fn find_mine_count_deductions(board: &Board, num_mines: u32) -> Vec<Deduction> {
+    let covered = get_covered(board);
+    let mines_left = get_mines_left(board, num_mines) as usize;
+    let mut deductions = Vec::new();
+    let mut seen = HashSet::new();
+
+    if !covered.is_empty() && mines_left == 0 {
+        push_all(&mut deductions, &mut seen, &covered, false, &Reason::MineCount);
+    } else if !covered.is_empty() && mines_left == covered.len() {
+        push_all(&mut deductions, &mut seen, &covered, true, &Reason::MineCount);
+    }
+
+    deductions
+}
+
+// everything that can be proven about the covered squares, using the simplest kind of
+// reasoning that finds anything at all
+pub fn find_deductions(board: &Board, num_mines: u32) -> Vec<Deduction> {
+    let constraints = get_constraints(board);
+
+    let deductions = find_single_deductions(&constraints);
+    if !deductions.is_empty() {
+        return deductions;
+    }
+
+    let deductions = find_pair_deductions(&constraints);
+    if !deductions.is_empty() {
+        return deductions;
+    }
+
+    let deductions = find_mine_count_deductions(board, num_mines);
+    if !deductions.is_empty() {
+        return deductions;
+    }
+
+    find_enumeration_deductions(board, num_mines)
+}
+
+// only once every arrangement has been counted, an estimate proves nothing
+fn find_enumeration_deductions(board: &Board, num_mines: u32) -> Vec<Deduction> {
+    let (probabilities, exact) = estimate_probabilities(board, num_mines);
+    if !exact {
+        return Vec::new();
+    }
+
+    let mut deductions: Vec<_> = probabilities
+        .into_iter()
+        .filter(|&(_, p)| !(EPSILON..=1.0 - EPSILON).contains(&p))
+        .map(|(pos, p)| Deduction {
+            pos,
+            is_mine: p > 0.5,
+            reason: Reason::Enumeration,
+        })
+        .collect();
+
+    deductions.sort_by_key(|d| (d.pos.0, d.pos.1));
+    deductions
+}
+
+fn get_components(constraints: &[Constraint]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of: Vec<Option<usize>> = vec![None; constraints.len()];
+
+    for start in 0..constraints.len() {
+        if group_of[start].is_some() {
+            continue;
+        }
+
+        let group = groups.len();
+        let mut members = vec![start];
+        let mut stack = vec![start];
+        group_of[start] = Some(group);
+
+        while let Some(curr) = stack.pop() {
+            for (other, c) in constraints.iter().enumerate() {
+                if group_of[other].is_none()
+                    && c.cells.iter().any(|x| constraints[curr].cells.contains(x))
+                {
+                    group_of[other] = Some(group);
+                    members.push(other);
+                    stack.push(other);
+                }
+            }
+        }
+
+        groups.push(members);
+    }
+
+    groups
+}
+
+struct Enumeration<'a> {
+    constraints: Vec<(Vec<usize>, u32)>,
+    cell_constraints: Vec<Vec<usize>>,
+    assigned: Vec<bool>,
+    placed: Vec<u32>,
+    open: Vec<u32>,
+    steps: u32,
+    result: &'a mut Component,
+}
+
+impl<'a> Enumeration<'a> {
+    // returns false once the step budget has run out
+    fn search(&mut self, index: usize, mines: usize) -> bool {
+        self.steps += 1;
+        if self.steps > MAX_ENUMERATION_STEPS {
+            return false;
+        }
+
+        if index == self.assigned.len() {
+            if self.constraints.iter().enumerate().all(|(c, x)| self.placed[c] == x.1) {
+                self.result.solutions[mines] += 1.0;
+                for (cell, is_mine) in self.assigned.iter().enumerate() {
+                    if *is_mine {
+                        self.result.cell_solutions[mines][cell] += 1.0;
+                    }
+                }
+            }
+            return true;
+        }
+
+        for &is_mine in &[false, true] {
+            let feasible = self.cell_constraints[index].iter().all(|&c| {
+                let placed = self.placed[c] + is_mine as u32;
+                let open = self.open[c] - 1;
+                placed <= self.constraints[c].1 && placed + open >= self.constraints[c].1
+            });
+
+            if !feasible {
+                continue;
+            }
+
+            self.assigned[index] = is_mine;
+            for &c in &self.cell_constraints[index] {
+                self.placed[c] += is_mine as u32;
+                self.open[c] -= 1;
+            }
+
+            let finished = self.search(index + 1, mines + is_mine as usize);
+
+            for &c in &self.cell_constraints[index] {
+                self.placed[c] -= is_mine as u32;
+                self.open[c] += 1;
+            }
+            self.assigned[index] = false;
+
+            if !finished {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn enumerate_component(constraints: &[Constraint], members: &[usize]) -> Option<Component> {
+    let mut cells: Vec<Position> = Vec::new();
+    for &c in members {
+        for pos in &constraints[c].cells {
+            if !cells.contains(pos) {
+                cells.push(*pos);
+            }
+        }
+    }
+
+    let local: Vec<(Vec<usize>, u32)> = members
+        .iter()
+        .map(|&c| {
+            let indices = constraints[c]
+                .cells
+                .iter()
+                .map(|pos| cells.iter().position(|x| x == pos).unwrap())
+                .collect();
+            (indices, constraints[c].mines)
+        })
+        .collect();
+
+    let cell_constraints = (0..cells.len())
+        .map(|cell| {
+            (0..local.len())
+                .filter(|&c| local[c].0.contains(&cell))
+                .collect()
+        })
+        .collect();
+
+    let mut result = Component {
+        solutions: vec![0.0; cells.len() + 1],
+        cell_solutions: vec![vec![0.0; cells.len()]; cells.len() + 1],
+        cells,
+    };
+
+    let finished = {
+        let mut enumeration = Enumeration {
+            open: local.iter().map(|x| x.0.len() as u32).collect(),
+            placed: vec![0; local.len()],
+            assigned: vec![false; result.cells.len()],
+            constraints: local,
+            cell_constraints,
+            steps: 0,
+            result: &mut result,
+        };
+        enumeration.search(0, 0)
+    };
+
+    if finished {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+fn ln_choose(n: u32, k: u32) -> Option<f64> {
+    if k > n {
+        return None;
+    }
+
+    let k = k.min(n - k);
+    Some((0..k).map(|i| f64::from(n - i).ln() - f64::from(i + 1).ln()).sum())
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+
+    result
+}
+
+// chance of each covered square hiding a mine, given the numbers and the mine count
+pub fn get_probabilities(board: &Board, num_mines: u32) -> HashMap<Position, f64> {
+    estimate_probabilities(board, num_mines).0
+}
+
+// the chances and whether they are exact, false when a component was too big to enumerate
+fn estimate_probabilities(board: &Board, num_mines: u32) -> (HashMap<Position, f64>, bool) {
+    let covered = get_covered(board);
+    let mines_left = get_mines_left(board, num_mines);
+    let constraints = get_constraints(board);
+    let mut probabilities = HashMap::new();
+
+    if covered.is_empty() {
+        return (probabilities, true);
+    }
+
+    let density = f64::from(mines_left) / covered.len() as f64;
+    let groups = get_components(&constraints);
+    let components: Vec<_> = groups
+        .iter()
+        .filter_map(|members| enumerate_component(&constraints, members))
+        .collect();
+    let exact = components.len() == groups.len();
+
+    let frontier: HashSet<Position> = components
+        .iter()
+        .flat_map(|c| c.cells.iter().cloned())
+        .collect();
+    let outside = (covered.len() - frontier.len()) as u32;
+
+    // weight of each total mine count in the frontier, scaled by the ways of placing the rest
+    let outside_weights = |dist: &[f64]| -> Vec<f64> {
+        let ln_weights: Vec<_> = (0..dist.len())
+            .map(|t| {
+                mines_left
+                    .checked_sub(t as u32)
+                    .and_then(|rest| ln_choose(outside, rest))
+            })
+            .collect();
+        let max = ln_weights
+            .iter()
+            .filter_map(|x| *x)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        ln_weights
+            .iter()
+            .map(|x| x.map_or(0.0, |w| (w - max).exp()))
+            .collect()
+    };
+
+    let total = components
+        .iter()
+        .fold(vec![1.0], |dist, c| convolve(&dist, &c.solutions));
+    let total_weights = outside_weights(&total);
+    let total_weight: f64 = total.iter().zip(&total_weights).map(|(d, w)| d * w).sum();
+
+    // numbers contradicting each other or the mine count, nothing better than a uniform guess
+    if total_weight <= 0.0 {
+        for pos in covered {
+            probabilities.insert(pos, density);
+        }
+        return (probabilities, exact);
+    }
+
+    for (index, component) in components.iter().enumerate() {
+        let others = components
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != index)
+            .fold(vec![1.0], |dist, (_, c)| convolve(&dist, &c.solutions));
+
+        for (cell, pos) in component.cells.iter().enumerate() {
+            let per_count: Vec<_> = component
+                .cell_solutions
+                .iter()
+                .map(|counts| counts[cell])
+                .collect();
+            let dist = convolve(&others, &per_count);
+            let weight: f64 = dist
+                .iter()
+                .zip(&total_weights)
+                .map(|(d, w)| d * w)
+                .sum();
+
+            probabilities.insert(*pos, weight / total_weight);
+        }
+    }
+
+    if outside > 0 {
+        let expected: f64 = total
+            .iter()
+            .zip(&total_weights)
+            .enumerate()
+            .map(|(t, (d, w))| d * w * f64::from(mines_left.saturating_sub(t as u32)))
+            .sum::<f64>() / total_weight;
+
+        for pos in covered {
+            probabilities
+                .entry(pos)
+                .or_insert(expected / f64::from(outside));
+        }
+    }
+
+    (probabilities, exact)
+}
+
+// the covered square least likely to be a mine, earliest in reading order on ties
+pub fn find_safest_guess(board: &Board, num_mines: u32) -> Option<(Position, f64)> {
+    let probabilities = get_probabilities(board, num_mines);
+
+    get_covered(board)
+        .into_iter()
+        .map(|pos| (pos, probabilities[&pos]))
+        .fold(None, |best, (pos, p)| match best {
+            Some((_, best_p)) if best_p <= p + EPSILON => best,
+            _ => Some((pos, p)),
+        })
+}
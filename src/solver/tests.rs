@@ -0,0 +1,166 @@
+use super::*;
+
+fn parse_board(rows: &[&str]) -> Vec<Vec<VisibleSquare>> {
+    rows.iter()
+        .map(|row| {
+            row.chars()
+                .map(|x| match x {
+                    '#' => VisibleSquare::Covered,
+                    'F' => VisibleSquare::Flagged,
+                    '.' => VisibleSquare::Revealed(0),
+                    _ => VisibleSquare::Revealed(x.to_digit(10).unwrap()),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_get_neighbors() {
+    let board = parse_board(&["###", "###"]);
+
+    assert_eq!(
+        get_neighbors(&board, &Position(0, 0)),
+        vec![Position(0, 1), Position(1, 0), Position(1, 1)]
+    );
+    assert_eq!(get_neighbors(&board, &Position(1, 1)).len(), 5);
+}
+
+#[test]
+fn test_single_deductions() {
+    let board = parse_board(&["#1."]);
+
+    assert_eq!(
+        find_deductions(&board, 1),
+        vec![Deduction {
+            pos: Position(0, 0),
+            is_mine: true,
+            reason: Reason::Single(Position(0, 1)),
+        }]
+    );
+
+    let board = parse_board(&["F1#"]);
+
+    assert_eq!(
+        find_deductions(&board, 1),
+        vec![Deduction {
+            pos: Position(0, 2),
+            is_mine: false,
+            reason: Reason::Single(Position(0, 1)),
+        }]
+    );
+}
+
+#[test]
+fn test_pair_deductions() {
+    // the 1-2-1 pattern, mines sit under both ones
+    let board = parse_board(&["###", "121"]);
+    let deductions = find_deductions(&board, 2);
+
+    assert_eq!(deductions.len(), 2);
+    assert!(deductions.contains(&Deduction {
+        pos: Position(0, 2),
+        is_mine: true,
+        reason: Reason::Pair(Position(1, 0), Position(1, 1)),
+    }));
+    assert!(deductions.contains(&Deduction {
+        pos: Position(0, 0),
+        is_mine: true,
+        reason: Reason::Pair(Position(1, 2), Position(1, 1)),
+    }));
+}
+
+#[test]
+fn test_mine_count_deductions() {
+    let board = parse_board(&["##", "##"]);
+    let deductions = find_deductions(&board, 4);
+
+    assert_eq!(deductions.len(), 4);
+    assert!(deductions
+        .iter()
+        .all(|x| x.is_mine && x.reason == Reason::MineCount));
+
+    let board = parse_board(&["F#"]);
+
+    assert_eq!(
+        find_deductions(&board, 1),
+        vec![Deduction {
+            pos: Position(0, 1),
+            is_mine: false,
+            reason: Reason::MineCount,
+        }]
+    );
+}
+
+#[test]
+fn test_enumeration_deductions() {
+    // the one mine has to be next to the 1, so the far square is safe
+    let board = parse_board(&["#1##"]);
+
+    assert_eq!(
+        find_deductions(&board, 1),
+        vec![Deduction {
+            pos: Position(0, 3),
+            is_mine: false,
+            reason: Reason::Enumeration,
+        }]
+    );
+}
+
+#[test]
+fn test_probabilities() {
+    let board = parse_board(&["#1#"]);
+    let probabilities = get_probabilities(&board, 1);
+
+    assert!((probabilities[&Position(0, 0)] - 0.5).abs() < 1e-9);
+    assert!((probabilities[&Position(0, 2)] - 0.5).abs() < 1e-9);
+
+    let board = parse_board(&["###", "###", "###"]);
+    let probabilities = get_probabilities(&board, 1);
+
+    assert_eq!(probabilities.len(), 9);
+    assert!(probabilities.values().all(|p| (p - 1.0 / 9.0).abs() < 1e-9));
+}
+
+#[test]
+fn test_safest_guess() {
+    // 3 mines left over 4 squares, the two next to the 1 share a single mine
+    let board = parse_board(&["#1###"]);
+    let (pos, p) = find_safest_guess(&board, 2).unwrap();
+
+    assert_eq!(pos, Position(0, 0));
+    assert!((p - 0.5).abs() < 1e-9);
+
+    assert_eq!(find_safest_guess(&parse_board(&["1F"]), 1), None);
+}
+
+#[test]
+fn test_enumeration_too_big() {
+    // every other square covered, each number sees two mines among four covered squares, far
+    // too many arrangements to count
+    let size: usize = 32;
+    let is_mine = |i: usize, j: usize| (i + j) % 2 == 1 && i.is_multiple_of(2);
+    let around = |i: usize| i.saturating_sub(1)..(i + 2).min(size);
+    let rows: Vec<String> = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    if (i + j) % 2 == 1 {
+                        return '#';
+                    }
+                    let mines = around(i)
+                        .flat_map(|y| around(j).map(move |x| (y, x)))
+                        .filter(|&(y, x)| is_mine(y, x))
+                        .count();
+                    ::std::char::from_digit(mines as u32, 10).unwrap()
+                })
+                .collect()
+        })
+        .collect();
+    let rows: Vec<_> = rows.iter().map(|x| &x[..]).collect();
+    let board = parse_board(&rows);
+    let num_mines = (size * size / 4) as u32;
+
+    assert!(!estimate_probabilities(&board, num_mines).1);
+    assert_eq!(find_enumeration_deductions(&board, num_mines), Vec::new());
+}
@@ -0,0 +1,459 @@
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+    KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use minesweep_rs::game::preset::PresetRegistry;
+use minesweep_rs::game::{GameState, LoseReason, MineSweeper, Position, Rules, SquareState};
+use minesweep_rs::palette::{self, Palette};
+use minesweep_rs::render;
+use std::cmp::min;
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+// board is drawn below the top bar, each cell two terminal columns wide
+const BOARD_ROW: u16 = 2;
+const CELL_WIDTH: u16 = 2;
+const TICK: Duration = Duration::from_millis(100);
+const UI_RECT_COLOR: Color = Color::Rgb { r: 77, g: 77, b: 77 };
+const UI_TEXT_COLOR: Color = Color::Rgb { r: 255, g: 117, b: 89 };
+const HEART_COLOR: Color = Color::Rgb { r: 230, g: 25, b: 51 };
+const CELL_COVERED_COLOR: Color = Color::Rgb { r: 230, g: 230, b: 230 };
+const CELL_REVEALED_COLOR: Color = Color::Rgb { r: 179, g: 179, b: 179 };
+const MINE_REVEALED_COLOR: Color = Color::Rgb { r: 179, g: 0, b: 0 };
+const FLAG_COLOR: Color = Color::Rgb { r: 204, g: 0, b: 0 };
+const HUNT_PLAYER_COLORS: [Color; 2] = [
+    Color::Rgb { r: 0, g: 89, b: 230 },
+    Color::Rgb { r: 242, g: 140, b: 0 },
+];
+const HELP_TEXT: &str =
+    "arrows/hjkl move  space reveal  f flag  d chord  u undo  r reset  p pause  1-9 preset  q quit";
+
+// puts the terminal into raw mode for the lifetime of the game and restores it afterwards,
+// even if the game panics
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new(out: &mut Stdout) -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            out,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange,
+            Hide
+        )?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            DisableFocusChange,
+            DisableMouseCapture,
+            Show,
+            LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub struct Tui {
+    game: MineSweeper,
+    cursor: Position,
+    presets: PresetRegistry,
+    // index of the preset being played
+    preset: usize,
+    rules: Rules,
+    // the number colors, terminals have no room for the window's patterns
+    palette: Palette,
+}
+
+impl Tui {
+    pub fn new(presets: PresetRegistry, preset: usize, rules: Rules) -> Tui {
+        Tui {
+            game: MineSweeper::new_from_preset(presets.get(preset).unwrap(), rules),
+            cursor: Position(0, 0),
+            presets,
+            preset,
+            rules,
+            palette: palette::DEFAULT,
+        }
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut out = io::stdout();
+        let _guard = TerminalGuard::new(&mut out)?;
+        queue!(out, Clear(ClearType::All))?;
+
+        loop {
+            // the countdown can run out without any input from the player
+            if self.game.state == GameState::Ongoing && self.game.rules.time_limit.is_some() {
+                self.game.update_game_state();
+            }
+
+            self.draw(&mut out)?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let keep_running = self.handle_key_press(key.code, &mut out)?;
+                    if !keep_running {
+                        return Ok(());
+                    }
+                }
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                Event::FocusLost => self.game.pause(),
+                Event::Resize(_, _) => queue!(out, Clear(ClearType::All))?,
+                _ => (),
+            }
+        }
+    }
+
+    fn select_preset(&mut self, index: usize, out: &mut Stdout) -> io::Result<()> {
+        let game = match self.presets.get(index) {
+            Some(preset) => MineSweeper::new_from_preset(preset, self.rules),
+            None => return Ok(()),
+        };
+
+        self.game = game;
+        self.preset = index;
+        self.cursor = Position(
+            min(self.cursor.0, self.game.rows - 1),
+            min(self.cursor.1, self.game.cols - 1),
+        );
+        queue!(out, Clear(ClearType::All))
+    }
+
+    // returns false once the player asks to quit
+    fn handle_key_press(&mut self, key: KeyCode, out: &mut Stdout) -> io::Result<bool> {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1, 0),
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(0, -1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(0, 1),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                let cursor = self.cursor;
+                self.reveal(&cursor);
+            }
+            KeyCode::Char('f') => {
+                let cursor = self.cursor;
+                self.flag(&cursor);
+            }
+            KeyCode::Char('d') => {
+                let cursor = self.cursor;
+                self.chord(&cursor);
+            }
+            KeyCode::Char('r') => self.game.reset(),
+            KeyCode::Char('u') => {
+                self.game.undo();
+            }
+            KeyCode::Char('p') => {
+                if self.game.is_paused() {
+                    self.game.resume();
+                } else {
+                    self.game.pause();
+                }
+            }
+            // the digit keys pick presets in the order they were registered
+            KeyCode::Char(x @ '1'..='9') => {
+                self.select_preset(x as usize - '1' as usize, out)?
+            }
+            _ => (),
+        }
+
+        Ok(true)
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let curr_pos = match self.get_board_position(mouse.column, mouse.row) {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        if let MouseEventKind::Down(_) | MouseEventKind::Moved = mouse.kind {
+            self.cursor = curr_pos;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Up(MouseButton::Left) => self.reveal(&curr_pos),
+            MouseEventKind::Down(MouseButton::Right) => self.flag(&curr_pos),
+            MouseEventKind::Down(MouseButton::Middle) => self.chord(&curr_pos),
+            _ => (),
+        }
+    }
+
+    fn get_board_position(&self, column: u16, row: u16) -> Option<Position> {
+        if row < BOARD_ROW {
+            return None;
+        }
+
+        let i = u32::from(row - BOARD_ROW);
+        let j = u32::from(column / CELL_WIDTH);
+
+        if i < self.game.rows && j < self.game.cols {
+            Some(Position(i, j))
+        } else {
+            None
+        }
+    }
+
+    fn move_cursor(&mut self, d_row: i64, d_col: i64) {
+        let row = i64::from(self.cursor.0) + d_row;
+        let col = i64::from(self.cursor.1) + d_col;
+
+        let rows = i64::from(self.game.rows);
+        let cols = i64::from(self.game.cols);
+
+        if row >= 0 && col >= 0 && row < rows && col < cols {
+            self.cursor = Position(row as u32, col as u32);
+        }
+    }
+
+    fn can_play(&self) -> bool {
+        self.game.state == GameState::Ongoing && !self.game.is_paused()
+    }
+
+    fn reveal(&mut self, curr_pos: &Position) {
+        if self.can_play() {
+            self.game.save_undo();
+            self.game.reveal_square(curr_pos);
+            self.game.first_moved();
+            self.game.update_game_state();
+            self.game.drop_undo_if_unchanged();
+        }
+    }
+
+    fn flag(&mut self, curr_pos: &Position) {
+        if self.can_play() {
+            self.game.save_undo();
+            self.game.toggle_flag_square(curr_pos);
+            self.game.update_game_state();
+            self.game.drop_undo_if_unchanged();
+        }
+    }
+
+    fn chord(&mut self, curr_pos: &Position) {
+        if self.can_play() {
+            self.game.save_undo();
+            self.game.try_reveal_adjacent(curr_pos);
+            self.game.update_game_state();
+            self.game.drop_undo_if_unchanged();
+        }
+    }
+
+    fn get_number_color(&self, num: u32) -> [f32; 4] {
+        match num {
+            1..=8 => self.palette.numbers[num as usize - 1],
+            _ => palette::get_number_color(num),
+        }
+    }
+
+    fn get_color(color: [f32; 4]) -> Color {
+        Color::Rgb {
+            r: (color[0] * 255.0) as u8,
+            g: (color[1] * 255.0) as u8,
+            b: (color[2] * 255.0) as u8,
+        }
+    }
+
+    fn get_time_text(&mut self) -> String {
+        let exact = self.game.state != GameState::Ongoing;
+        match (self.game.lose_reason(), self.game.get_time_left_ms()) {
+            (Some(LoseReason::TimeUp), _) => "TIME".to_owned(),
+            (_, Some(time_left)) => render::format_time(time_left, exact),
+            (_, None) => render::format_time(self.game.elapsed_ms(), exact),
+        }
+    }
+
+    fn draw(&mut self, out: &mut Stdout) -> io::Result<()> {
+        self.draw_top_bar(out)?;
+
+        for i in 0..self.game.rows {
+            queue!(out, MoveTo(0, BOARD_ROW + i as u16))?;
+
+            for j in 0..self.game.cols {
+                if self.game.is_paused() {
+                    queue!(out, SetBackgroundColor(UI_RECT_COLOR), Print("  "))?;
+                    continue;
+                }
+
+                if Position(i, j) == self.cursor {
+                    queue!(out, SetAttribute(Attribute::Reverse))?;
+                }
+
+                let square = *self.game.get_square(i, j);
+                match square.state {
+                    SquareState::Covered => {
+                        queue!(out, SetBackgroundColor(CELL_COVERED_COLOR), Print("  "))?;
+                    }
+                    SquareState::Flagged => {
+                        let background = if square.exploded {
+                            MINE_REVEALED_COLOR
+                        } else {
+                            CELL_COVERED_COLOR
+                        };
+                        let foreground = if square.exploded {
+                            Color::White
+                        } else {
+                            FLAG_COLOR
+                        };
+
+                        queue!(
+                            out,
+                            SetBackgroundColor(background),
+                            SetForegroundColor(foreground),
+                            Print("F ")
+                        )?;
+                    }
+                    SquareState::Revealed if square.is_mine => {
+                        let background = match square.claimed_by {
+                            Some(player) => HUNT_PLAYER_COLORS[player],
+                            None if square.exploded => MINE_REVEALED_COLOR,
+                            None => CELL_REVEALED_COLOR,
+                        };
+
+                        queue!(
+                            out,
+                            SetBackgroundColor(background),
+                            SetForegroundColor(Color::Black),
+                            Print("* ")
+                        )?;
+                    }
+                    SquareState::Revealed => {
+                        let label = if square.adjacent_mines > 0 {
+                            format!("{} ", square.adjacent_mines)
+                        } else {
+                            "  ".to_owned()
+                        };
+
+                        queue!(
+                            out,
+                            SetBackgroundColor(CELL_REVEALED_COLOR),
+                            SetForegroundColor(Tui::get_color(
+                                self.get_number_color(square.adjacent_mines)
+                            )),
+                            SetAttribute(Attribute::Bold),
+                            Print(label)
+                        )?;
+                    }
+                }
+
+                queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+            }
+
+            queue!(out, ResetColor)?;
+        }
+
+        if self.game.is_paused() {
+            let width = self.game.cols as u16 * CELL_WIDTH;
+            queue!(
+                out,
+                MoveTo((width / 2).saturating_sub(3), BOARD_ROW + self.game.rows as u16 / 2),
+                SetBackgroundColor(UI_RECT_COLOR),
+                SetForegroundColor(UI_TEXT_COLOR),
+                Print("PAUSED"),
+                ResetColor
+            )?;
+        }
+
+        queue!(
+            out,
+            MoveTo(0, BOARD_ROW + self.game.rows as u16 + 1),
+            Print(HELP_TEXT)
+        )?;
+
+        out.flush()
+    }
+
+    fn draw_top_bar(&mut self, out: &mut Stdout) -> io::Result<()> {
+        let face = match self.game.state {
+            GameState::Ongoing => ":)",
+            GameState::Won => "B)",
+            GameState::Lost => ":(",
+        };
+        let time_text = self.get_time_text();
+
+        queue!(out, MoveTo(0, 0), Clear(ClearType::CurrentLine))?;
+
+        if self.game.rules.mine_hunt {
+            return self.draw_scores(out, face);
+        }
+
+        queue!(
+            out,
+            SetBackgroundColor(UI_RECT_COLOR),
+            SetForegroundColor(UI_TEXT_COLOR),
+            Print(format!(" {:03} ", self.game.get_flags_left())),
+            ResetColor
+        )?;
+
+        if self.game.rules.lives > 1 {
+            queue!(
+                out,
+                SetForegroundColor(HEART_COLOR),
+                Print(format!(" \u{2665}{}", self.game.get_lives_left())),
+                ResetColor
+            )?;
+        }
+
+        queue!(
+            out,
+            Print(" "),
+            SetBackgroundColor(CELL_COVERED_COLOR),
+            SetForegroundColor(Color::Black),
+            Print(format!(" {} ", face)),
+            ResetColor,
+            Print(" "),
+            SetBackgroundColor(UI_RECT_COLOR),
+            SetForegroundColor(UI_TEXT_COLOR),
+            Print(format!(" {} ", time_text)),
+            ResetColor,
+            Print(format!(" {}", self.presets.get(self.preset).unwrap().name))
+        )
+    }
+
+    // mine hunt has no flags or clock worth showing, only both scores with the mover marked
+    fn draw_scores(&mut self, out: &mut Stdout, face: &str) -> io::Result<()> {
+        let scores = self.game.get_scores();
+        let highlighted = match self.game.state {
+            GameState::Ongoing => Some(self.game.get_turn()),
+            _ => self.game.get_winner(),
+        };
+
+        for player in 0..2 {
+            let marker = if highlighted == Some(player) { '>' } else { ' ' };
+
+            queue!(
+                out,
+                SetBackgroundColor(UI_RECT_COLOR),
+                SetForegroundColor(HUNT_PLAYER_COLORS[player]),
+                Print(format!("{}P{} {:03} ", marker, player + 1, scores[player])),
+                ResetColor,
+                Print(" ")
+            )?;
+        }
+
+        queue!(
+            out,
+            SetBackgroundColor(CELL_COVERED_COLOR),
+            SetForegroundColor(Color::Black),
+            Print(format!(" {} ", face)),
+            ResetColor
+        )
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+use toml;
+
+use bot::Action;
+use game::layout::LayoutError;
+use game::{GameState, MineSweeper, Position, Rules};
+
+pub const LESSON_EXTENSION: &str = "toml";
+
+// a lesson file, positions are [row, col] counted from 0 at the top left
+//
+//   title = "The 1-2-1 pattern"
+//   board = """
+//   #*#*#
+//   11211
+//   .....
+//   """
+//
+//   [[steps]]
+//   text = "The 2 can not have both its mines next to one 1."
+//   point = [[1, 1], [1, 2]]
+//   flag = [0, 3]
+//
+// the board is a layout like render --board takes, and every step moves on once the player
+// makes its reveal, flag or chord, or with any reveal when it has none
+#[derive(Deserialize)]
+struct LessonFile {
+    title: String,
+    board: String,
+    steps: Vec<StepFile>,
+}
+
+#[derive(Deserialize)]
+struct StepFile {
+    text: String,
+    #[serde(default)]
+    point: Vec<[u32; 2]>,
+    reveal: Option<[u32; 2]>,
+    flag: Option<[u32; 2]>,
+    chord: Option<[u32; 2]>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Step {
+    pub text: String,
+    // squares the step points at
+    pub marks: Vec<Position>,
+    // the move the step waits for, None to go on with any reveal
+    pub action: Option<Action>,
+}
+
+impl Step {
+    // whether the move finishes the step, anything else is ignored
+    pub fn accepts(&self, action: &Action) -> bool {
+        match self.action {
+            Some(expected) => expected == *action,
+            None => matches!(*action, Action::Reveal(_)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Lesson {
+    pub title: String,
+    pub board: String,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+pub enum LessonError {
+    Toml(toml::de::Error),
+    Layout(LayoutError),
+    NoSteps,
+    // steps are numbered from 1 in the order they appear in the file
+    OutOfBounds(usize),
+    TwoMoves(usize),
+    // the move is made after the board is already won or lost
+    GameOver(usize),
+    // the move leaves the board as it was
+    NoEffect(usize),
+}
+
+impl fmt::Display for LessonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LessonError::Toml(ref err) => write!(f, "{}", err),
+            LessonError::Layout(err) => write!(f, "board: {}", err),
+            LessonError::NoSteps => write!(f, "no steps"),
+            LessonError::OutOfBounds(step) => write!(f, "step {} is off the board", step),
+            LessonError::TwoMoves(step) => write!(f, "step {} waits for more than one move", step),
+            LessonError::GameOver(step) => write!(f, "step {} comes after the game is over", step),
+            LessonError::NoEffect(step) => write!(f, "step {} does not change the board", step),
+        }
+    }
+}
+
+impl Lesson {
+    // the lesson's moves are played through on its board, so a lesson that loads can be
+    // finished
+    pub fn parse(text: &str) -> Result<Lesson, LessonError> {
+        let file: LessonFile = toml::from_str(text).map_err(LessonError::Toml)?;
+        let mut game =
+            MineSweeper::from_layout(&file.board, Rules::default()).map_err(LessonError::Layout)?;
+        if file.steps.is_empty() {
+            return Err(LessonError::NoSteps);
+        }
+
+        let mut steps = Vec::new();
+        for (i, step) in file.steps.into_iter().enumerate() {
+            let number = i + 1;
+            let on_board = |x: &[u32; 2]| x[0] < game.rows && x[1] < game.cols;
+            let moves = [
+                step.reveal.map(|x| (Action::Reveal(Position(x[0], x[1])), x)),
+                step.flag.map(|x| (Action::Flag(Position(x[0], x[1])), x)),
+                step.chord.map(|x| (Action::Chord(Position(x[0], x[1])), x)),
+            ];
+            let moves: Vec<_> = moves.iter().filter_map(|x| *x).collect();
+
+            if !step.point.iter().chain(moves.iter().map(|x| &x.1)).all(on_board) {
+                return Err(LessonError::OutOfBounds(number));
+            }
+            if moves.len() > 1 {
+                return Err(LessonError::TwoMoves(number));
+            }
+
+            let action = moves.first().map(|x| x.0);
+            if let Some(action) = action {
+                if game.state != GameState::Ongoing {
+                    return Err(LessonError::GameOver(number));
+                }
+
+                let before = game.get_visible_board();
+                action.apply(&mut game);
+                if game.get_visible_board() == before {
+                    return Err(LessonError::NoEffect(number));
+                }
+            }
+
+            steps.push(Step {
+                text: step.text.trim().to_owned(),
+                marks: step.point.iter().map(|x| Position(x[0], x[1])).collect(),
+                action,
+            });
+        }
+
+        Ok(Lesson {
+            title: file.title,
+            board: file.board,
+            steps,
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Lesson> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+
+        Lesson::parse(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), err),
+            )
+        })
+    }
+
+    // the board as the lesson starts, played by the classic rules
+    pub fn new_game(&self) -> MineSweeper {
+        MineSweeper::from_layout(&self.board, Rules::default()).unwrap()
+    }
+}
+
+// every lesson file in dir in the order of their names, a missing dir has none
+pub fn find_lessons(dir: &Path) -> Vec<io::Result<Lesson>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|x| x == LESSON_EXTENSION))
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|path| Lesson::load(path)).collect()
+}
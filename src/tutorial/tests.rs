@@ -0,0 +1,97 @@
+use super::*;
+
+use std::env;
+
+const LESSON: &str = r#"
+title = "Test"
+board = """
+*1.
+"""
+
+[[steps]]
+text = "Go on."
+point = [[0, 1]]
+
+[[steps]]
+text = "Flag it."
+flag = [0, 0]
+"#;
+
+#[test]
+fn test_parse() {
+    let lesson = Lesson::parse(LESSON).unwrap();
+
+    assert_eq!(lesson.title, "Test");
+    assert_eq!(
+        lesson.steps,
+        vec![
+            Step {
+                text: "Go on.".to_owned(),
+                marks: vec![Position(0, 1)],
+                action: None,
+            },
+            Step {
+                text: "Flag it.".to_owned(),
+                marks: Vec::new(),
+                action: Some(Action::Flag(Position(0, 0))),
+            },
+        ]
+    );
+    assert_eq!(lesson.new_game().get_layout(), "*1.");
+
+    let errors = [
+        (LESSON.replace("flag = [0, 0]", "flag = [0, 3]"), "step 2 is off the board"),
+        (LESSON.replace("flag = [0, 0]", "reveal = [0, 1]"), "step 2 does not change the board"),
+        (
+            LESSON.replace("flag = [0, 0]", "flag = [0, 0]\nchord = [0, 1]"),
+            "step 2 waits for more than one move",
+        ),
+        (LESSON.replace("*1.", "*2."), "board: number at 0 1 does not match the mines around it"),
+    ];
+    for &(ref text, message) in &errors {
+        assert_eq!(Lesson::parse(text).unwrap_err().to_string(), message);
+    }
+    assert!(Lesson::parse("title = \"Test\"\nboard = \"*1\"\nsteps = []").is_err());
+}
+
+#[test]
+fn test_accepts() {
+    let lesson = Lesson::parse(LESSON).unwrap();
+
+    // a step without a move goes on with any reveal
+    assert!(lesson.steps[0].accepts(&Action::Reveal(Position(0, 2))));
+    assert!(!lesson.steps[0].accepts(&Action::Flag(Position(0, 0))));
+    assert!(lesson.steps[1].accepts(&Action::Flag(Position(0, 0))));
+    assert!(!lesson.steps[1].accepts(&Action::Reveal(Position(0, 0))));
+    assert!(!lesson.steps[1].accepts(&Action::Flag(Position(0, 2))));
+}
+
+#[test]
+fn test_lessons() {
+    let dir = env::temp_dir().join(format!("minesweep-rs-lessons-{}", ::std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("2.toml"), LESSON.replace("Test", "Second")).unwrap();
+    fs::write(dir.join("1.toml"), LESSON).unwrap();
+    fs::write(dir.join("notes.txt"), "not a lesson").unwrap();
+
+    let titles: Vec<_> = find_lessons(&dir)
+        .into_iter()
+        .map(|x| x.unwrap().title)
+        .collect();
+    assert_eq!(titles, vec!["Test", "Second"]);
+    fs::remove_dir_all(&dir).unwrap();
+
+    // the lessons that ship with the game end with every mine flagged
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join("lessons");
+    let lessons = find_lessons(&dir);
+    assert!(!lessons.is_empty());
+
+    for lesson in lessons {
+        let lesson = lesson.unwrap();
+        let mut game = lesson.new_game();
+        for action in lesson.steps.iter().filter_map(|x| x.action) {
+            action.apply(&mut game);
+        }
+        assert_eq!(game.state, GameState::Won, "{}", lesson.title);
+    }
+}